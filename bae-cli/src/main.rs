@@ -1,26 +1,32 @@
 mod cli_io;
 mod diff;
 
-use bae_common::blog::BlogPost;
+use bae_common::blog::{
+    AuthorLink, AuthorProfile, BlogPost, BlogPostRevision, PreviewToken, SeriesPart,
+};
 use bae_common::database;
-use bae_common::database::{Author, Tag};
+use bae_common::database::{
+    Author, BlogStore, Language, MemoryStore, PostSelection, PostStatus, Series, Tag,
+};
 use bae_common::highlighting::Theme;
 use bae_common::markdown_render::{
-    render_md_to_html, CodeBlockHighlighter, RenderResult, StandardClassNameGenerator,
+    local_image_paths, render_md_to_html, strip_metadata_block, CodeBlockHighlighter, RenderResult,
+    StandardClassNameGenerator, RENDERER_VERSION,
 };
+use bae_common::media::{hash_from_path, MediaAsset};
 use chrono::{DateTime, Duration, Utc};
 use clap::{Parser, Subcommand};
 use color_eyre::eyre::{eyre, OptionExt, WrapErr};
-use serde::Deserialize;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use std::fs::File;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
-/// Probably slightly low-ball estimate but that's fine, it's a technical blog.
-const AVERAGE_READING_WPM: usize = 200;
-
 #[derive(Clone, Eq, PartialEq, Debug, Subcommand)]
 enum Command {
     GenerateHighlightCss {
@@ -34,8 +40,9 @@ enum Command {
     UploadBlogPost {
         #[arg(short, long)]
         md_file: PathBuf,
+        /// Only check that the post can be rendered and stored, without touching the database
         #[arg(long)]
-        new_author: bool,
+        dry_run: bool,
     },
     UpdateBlogPost {
         #[arg(short, long)]
@@ -43,8 +50,86 @@ enum Command {
         #[arg(short, long)]
         original_url: Option<String>,
         #[arg(long)]
-        new_author: bool,
+        changed_by: Option<Author>,
+    },
+    Sync {
+        dir: PathBuf,
+        /// Also delete posts that have no markdown file in `dir`
+        #[arg(long)]
+        prune: bool,
+        #[arg(long)]
+        changed_by: Option<Author>,
+    },
+    Export {
+        #[arg(short, long)]
+        output_dir: PathBuf,
+    },
+    Rerender {
+        /// Only show how the html would change
+        #[arg(long)]
+        dry_run: bool,
     },
+    ListRevisions {
+        #[arg(short, long)]
+        url: String,
+    },
+    DiffRevisions {
+        #[arg(long)]
+        old: i64,
+        /// Compares against the current state of the post if not given
+        #[arg(long)]
+        new: Option<i64>,
+    },
+    RollbackBlogPost {
+        #[arg(short, long)]
+        url: String,
+        #[arg(short, long)]
+        revision: i64,
+        #[arg(long)]
+        changed_by: Option<Author>,
+    },
+    DeleteBlogPost {
+        #[arg(short, long)]
+        url: String,
+    },
+    ArchiveBlogPost {
+        #[arg(short, long)]
+        url: String,
+        /// Marks the post as no longer archived instead
+        #[arg(long)]
+        unarchive: bool,
+        #[arg(long)]
+        changed_by: Option<Author>,
+    },
+    UnpublishBlogPost {
+        #[arg(short, long)]
+        url: String,
+        #[arg(long)]
+        changed_by: Option<Author>,
+    },
+    CreatePreviewToken {
+        #[arg(short, long)]
+        url: String,
+        #[arg(long)]
+        created_by: Option<Author>,
+    },
+    ListPreviewTokens {
+        #[arg(short, long)]
+        url: String,
+    },
+    RevokePreviewToken {
+        #[arg(short, long)]
+        token: String,
+    },
+    UploadAuthor {
+        #[arg(short, long)]
+        md_file: PathBuf,
+    },
+    UpdateAuthor {
+        #[arg(short, long)]
+        md_file: PathBuf,
+    },
+    ListAuthors,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Parser)]
@@ -74,15 +159,43 @@ async fn main() -> color_eyre::Result<()> {
             output_file,
             check,
         } => generate_highlight_css(&input_theme, &output_file, check),
-        Command::UploadBlogPost {
-            md_file,
-            new_author,
-        } => upload_blog_post(&md_file, new_author).await,
+        Command::UploadBlogPost { md_file, dry_run } => upload_blog_post(&md_file, dry_run).await,
         Command::UpdateBlogPost {
             md_file,
             original_url,
-            new_author,
-        } => update_blog_post(&md_file, original_url.as_deref(), new_author).await,
+            changed_by,
+        } => update_blog_post(&md_file, original_url.as_deref(), changed_by.as_ref()).await,
+        Command::Sync {
+            dir,
+            prune,
+            changed_by,
+        } => sync(&dir, prune, changed_by.as_ref()).await,
+        Command::Export { output_dir } => export(&*connect_database().await?, &output_dir).await,
+        Command::Rerender { dry_run } => rerender(dry_run).await,
+        Command::ListRevisions { url } => list_revisions(&url).await,
+        Command::DiffRevisions { old, new } => diff_revisions(old, new).await,
+        Command::RollbackBlogPost {
+            url,
+            revision,
+            changed_by,
+        } => rollback_blog_post(&url, revision, changed_by.as_ref()).await,
+        Command::DeleteBlogPost { url } => delete_blog_post(&url).await,
+        Command::ArchiveBlogPost {
+            url,
+            unarchive,
+            changed_by,
+        } => archive_blog_post(&url, !unarchive, changed_by.as_ref()).await,
+        Command::UnpublishBlogPost { url, changed_by } => {
+            unpublish_blog_post(&url, changed_by.as_ref()).await
+        }
+        Command::CreatePreviewToken { url, created_by } => {
+            create_preview_token(&url, created_by.as_ref()).await
+        }
+        Command::ListPreviewTokens { url } => list_preview_tokens(&url).await,
+        Command::RevokePreviewToken { token } => revoke_preview_token(&token).await,
+        Command::UploadAuthor { md_file } => upload_author(&md_file).await,
+        Command::UpdateAuthor { md_file } => update_author(&md_file).await,
+        Command::ListAuthors => list_authors().await,
     }
 }
 
@@ -131,17 +244,97 @@ fn generate_highlight_css(
     Ok(())
 }
 
-#[derive(Clone, Eq, PartialEq, Debug, Deserialize)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 struct FrontMatter {
     pub url: String,
     pub title: String,
-    pub description: String,
-    pub author: Author,
+    /// Without one, lists and feeds show an excerpt of the post instead.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Single author, kept for compatibility with older posts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<Author>,
+    /// Comes after `author` if both are given.
+    #[serde(default)]
+    pub authors: Vec<Author>,
     pub tags: Vec<Tag>,
-    pub accessible: bool,
+    pub status: PostStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub publication_date: Option<DateTime<Utc>>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reading_time_minutes: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series: Option<Series>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series_part: Option<u32>,
+    #[serde(default = "default_language")]
+    pub language: Language,
+    /// Shared by all translations of the same article.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation_key: Option<String>,
+    /// Listed before all other posts on the blog home.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// Shown in the featured sections of the blog home and the site home.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub featured: bool,
+    /// Shows a table of contents built from the headings above the post.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub table_of_contents: bool,
+}
+
+impl From<&BlogPost> for FrontMatter {
+    /// Front matter that uploads as `blog_post` again.
+    fn from(blog_post: &BlogPost) -> Self {
+        FrontMatter {
+            url: blog_post.url.clone(),
+            title: blog_post.title.clone(),
+            description: blog_post.description.clone(),
+            author: None,
+            authors: blog_post.authors.clone(),
+            tags: blog_post.tags.clone(),
+            status: blog_post.status,
+            publication_date: blog_post.publication_date,
+            reading_time_minutes: u32::try_from(blog_post.reading_time.num_minutes()).ok(),
+            series: blog_post
+                .series
+                .as_ref()
+                .map(|series| series.series.clone()),
+            series_part: blog_post.series.as_ref().map(|series| series.part),
+            language: blog_post.language.clone(),
+            translation_key: blog_post.translation_key.clone(),
+            pinned: blog_post.pinned,
+            featured: blog_post.featured,
+            table_of_contents: blog_post.table_of_contents,
+        }
+    }
+}
+
+fn default_language() -> Language {
+    Language("en-gb".to_string())
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+struct AuthorFrontMatter {
+    pub author: Author,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<AuthorLink>,
+}
+
+impl From<&AuthorProfile> for AuthorFrontMatter {
+    /// Front matter that uploads as `author_profile` again, together with its bio.
+    fn from(author_profile: &AuthorProfile) -> Self {
+        AuthorFrontMatter {
+            author: author_profile.author.clone(),
+            display_name: author_profile.display_name.clone(),
+            avatar_path: author_profile.avatar_path.clone(),
+            links: author_profile.links.clone(),
+        }
+    }
 }
 
 fn md_options() -> pulldown_cmark::Options {
@@ -156,20 +349,70 @@ fn md_options() -> pulldown_cmark::Options {
         | Opt::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS
 }
 
-fn reading_time(contents: &str) -> Duration {
-    Duration::minutes((contents.split_whitespace().count() / AVERAGE_READING_WPM) as i64)
+/// Reads the local images referenced by the markdown in `md_file`.
+/// Returns the assets together with the urls the images should be rendered with.
+fn media_assets_from_md(
+    markdown: &str,
+    md_file: &Path,
+) -> color_eyre::Result<(Vec<MediaAsset>, HashMap<String, String>)> {
+    let base_dir = md_file.parent().unwrap_or(Path::new(""));
+
+    let mut media_assets = Vec::new();
+    let mut image_urls = HashMap::new();
+    for image_path in local_image_paths(markdown, md_options()) {
+        let file = base_dir.join(&image_path);
+        let data = std::fs::read(&file)
+            .wrap_err_with(|| format!("Reading image {} failed", file.display()))?;
+        let extension = file
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default();
+        let media_asset = MediaAsset::new(data, extension)
+            .ok_or_else(|| eyre!("Image {} has an unsupported file type", file.display()))?;
+
+        image_urls.insert(image_path, media_asset.path());
+        media_assets.push(media_asset);
+    }
+
+    Ok((media_assets, image_urls))
 }
 
-fn full_blog_post_from_md(markdown: String) -> color_eyre::Result<BlogPost> {
-    let options = md_options();
+/// Reads and renders the post in `md_file` together with the images it references.
+fn blog_post_from_md_file(md_file: &Path) -> color_eyre::Result<(BlogPost, Vec<MediaAsset>)> {
+    let markdown = std::fs::read_to_string(md_file)?;
 
-    let RenderResult { metadata, html } = render_md_to_html(
-        &markdown,
-        options,
+    let (media_assets, image_urls) = media_assets_from_md(&markdown, md_file)?;
+    let full_post = full_blog_post_from_md(markdown, image_urls)?;
+
+    Ok((full_post, media_assets))
+}
+
+fn render_blog_post_md(
+    markdown: &str,
+    image_urls: &HashMap<String, String>,
+) -> color_eyre::Result<RenderResult> {
+    render_md_to_html(
+        markdown,
+        md_options(),
         &CodeBlockHighlighter::standard_config()
             .wrap_err("Getting standard CodeBlockHighlighter config failed")?,
+        image_urls,
     )
-    .wrap_err("Rendering markdown failed")?;
+    .wrap_err("Rendering markdown failed")
+}
+
+fn full_blog_post_from_md(
+    markdown: String,
+    image_urls: HashMap<String, String>,
+) -> color_eyre::Result<BlogPost> {
+    let RenderResult {
+        metadata,
+        html,
+        outline,
+        text_stats,
+        excerpt,
+        plain_text,
+    } = render_blog_post_md(&markdown, &image_urls)?;
 
     let metadata =
         metadata.ok_or_eyre("Blog post did not have correct pluses delimited metadata")?;
@@ -179,47 +422,182 @@ fn full_blog_post_from_md(markdown: String) -> color_eyre::Result<BlogPost> {
         title,
         description,
         author,
+        authors,
         tags,
-        accessible,
+        status,
         publication_date,
         reading_time_minutes,
+        series,
+        series_part,
+        language,
+        translation_key,
+        pinned,
+        featured,
+        table_of_contents,
     } = serde_json::from_str(&metadata).wrap_err("Front matter could not be parsed")?;
 
+    let authors: Vec<_> = author.into_iter().chain(authors).unique().collect();
+    if authors.is_empty() {
+        return Err(eyre!("Front matter must have an author or authors"));
+    }
+
+    if status.is_released() && publication_date.is_none() {
+        return Err(eyre!(
+            "Front matter must have a publication_date for status {status}"
+        ));
+    }
+
+    if !status.is_released() && publication_date.is_some() {
+        return Err(eyre!(
+            "Front matter must not have a publication_date for status {status}"
+        ));
+    }
+
+    let series = match (series, series_part) {
+        (Some(series), Some(part)) => Some(SeriesPart { series, part }),
+        (None, None) => None,
+        _ => {
+            return Err(eyre!(
+                "Front matter must have either both or neither of series and series_part"
+            ))
+        }
+    };
+
     let reading_time = reading_time_minutes
         .map(|minutes| Duration::minutes(minutes as i64))
-        .unwrap_or_else(|| reading_time(&markdown));
+        .unwrap_or_else(|| text_stats.reading_time());
 
     Ok(BlogPost {
         url,
         title,
         description,
-        author,
+        authors,
         markdown: Some(markdown),
         html,
         tags,
         reading_time,
-        accessible,
+        word_count: text_stats.words,
+        excerpt,
+        plain_text,
+        status,
         publication_date,
+        // Set by the store when the post is written
+        updated_at: DateTime::UNIX_EPOCH,
+        series,
+        language,
+        translation_key,
+        renderer_version: RENDERER_VERSION,
+        image_urls,
+        pinned,
+        featured,
+        outline,
+        table_of_contents,
+    })
+}
+
+fn author_profile_from_md(markdown: String) -> color_eyre::Result<AuthorProfile> {
+    let RenderResult { metadata, html, .. } = render_md_to_html(
+        &markdown,
+        md_options(),
+        &CodeBlockHighlighter::standard_config()
+            .wrap_err("Getting standard CodeBlockHighlighter config failed")?,
+        &HashMap::new(),
+    )
+    .wrap_err("Rendering markdown failed")?;
+
+    let metadata =
+        metadata.ok_or_eyre("Author profile did not have correct pluses delimited metadata")?;
+
+    let AuthorFrontMatter {
+        author,
+        display_name,
+        avatar_path,
+        links,
+    } = serde_json::from_str(&metadata).wrap_err("Front matter could not be parsed")?;
+
+    // A file with only front matter has no bio
+    let (bio_markdown, bio_html) = if html.trim().is_empty() {
+        (None, None)
+    } else {
+        (Some(markdown), Some(html))
+    };
+
+    Ok(AuthorProfile {
+        author,
+        display_name,
+        bio_markdown,
+        bio_html,
+        avatar_path,
+        links,
     })
 }
 
-async fn connect_database() -> color_eyre::Result<PgPool> {
-    let database_url = std::env::var("DATABASE_URL").wrap_err("DATABASE_URL env var error")?;
+fn main_author(post: &BlogPost) -> color_eyre::Result<&Author> {
+    post.authors
+        .first()
+        .ok_or_eyre("Post has no authors, specify who made the change")
+}
+
+fn database_url() -> color_eyre::Result<String> {
+    std::env::var("DATABASE_URL").wrap_err("DATABASE_URL env var error")
+}
+
+/// Connects to the backend selected by the scheme of `DATABASE_URL`.
+async fn connect_database() -> color_eyre::Result<Arc<dyn BlogStore>> {
+    let database_url = database_url()?;
+
+    let database = database::connect(&database_url)
+        .await
+        .wrap_err("Could not connect to database")?;
+
+    // Local SQLite databases are set up on first use, Postgres is migrated by the server
+    if database_url.starts_with("sqlite:") {
+        database
+            .migrate()
+            .await
+            .wrap_err("Migrating database failed")?;
+    }
+
+    Ok(database)
+}
+
+/// For commands that need features only the Postgres backend has.
+async fn connect_postgres() -> color_eyre::Result<PgPool> {
+    let database_url = database_url()?;
+
+    if database_url.starts_with("sqlite:") {
+        return Err(eyre!("This command needs a Postgres database"));
+    }
 
     PgPool::connect(&database_url)
         .await
         .wrap_err("Could not connect to database")
 }
 
-async fn upload_blog_post(md_file: &Path, new_author: bool) -> color_eyre::Result<()> {
-    let markdown = std::fs::read_to_string(md_file)?;
+/// With `dry_run`, the post is stored in memory only, which checks it without needing a database.
+async fn upload_blog_post(md_file: &Path, dry_run: bool) -> color_eyre::Result<()> {
+    let (full_post, media_assets) = blog_post_from_md_file(md_file)?;
 
-    let full_post = full_blog_post_from_md(markdown)?;
+    if dry_run {
+        MemoryStore::new()
+            .insert_blog_post(&full_post, &media_assets)
+            .await
+            .wrap_err("Inserting blog post failed")?;
+
+        println!(
+            "Dry run: {} ({}) would be uploaded as {} with {} media assets",
+            full_post.title,
+            full_post.url,
+            full_post.status,
+            media_assets.len(),
+        );
+        return Ok(());
+    }
 
-    if full_post.publication_date.is_some()
+    if full_post.status.is_released()
         && !cli_io::prompt(
-            "You are attempting to upload a blog post that has a publishing date. \
-            Usually you might want to look at how it renders before deciding a publication date. \
+            "You are attempting to upload a blog post that will be published. \
+            Usually you might want to look at how it renders as unlisted before publishing it. \
             Continue?",
         )?
     {
@@ -228,54 +606,825 @@ async fn upload_blog_post(md_file: &Path, new_author: bool) -> color_eyre::Resul
 
     let database = connect_database().await?;
 
-    let mut transaction = database.begin().await?;
-    database::insert_blog_post(&full_post, new_author, &mut transaction)
-        .await
-        .wrap_err("Inserting blog post failed before transaction commit")?;
-
-    transaction
-        .commit()
+    database
+        .insert_blog_post(&full_post, &media_assets)
         .await
         .wrap_err("Inserting blog post failed")
 }
 
+fn print_blog_post_diff(old: &BlogPost, new: &BlogPost) {
+    if let (Some(old_md), Some(new_md)) = (&old.markdown, &new.markdown) {
+        println!("Markdown Diff:");
+        println!();
+        diff::print_diff(old_md, new_md);
+        println!();
+    }
+
+    println!("HTML Diff:");
+    println!();
+    diff::print_diff(&old.html, &new.html);
+    println!();
+}
+
 async fn update_blog_post(
     md_file: &Path,
     original_url: Option<&str>,
-    new_author: bool,
+    changed_by: Option<&Author>,
 ) -> color_eyre::Result<()> {
-    let markdown = std::fs::read_to_string(md_file)?;
+    let (full_post, media_assets) = blog_post_from_md_file(md_file)?;
+
+    let database = connect_database().await?;
+
+    let old_full_post = database
+        .get_blog_post(original_url.unwrap_or(&full_post.url), false)
+        .await?
+        .ok_or_eyre("Post with original url not found")?;
+
+    print_blog_post_diff(&old_full_post, &full_post);
+
+    if !cli_io::prompt("Continue with update?").wrap_err("Prompting user failed")? {
+        return Err(eyre!("User aborted"));
+    }
+
+    let changed_by = changed_by.map_or_else(|| main_author(&full_post), Ok)?;
+
+    database
+        .update_blog_post(original_url, &full_post, changed_by, &media_assets)
+        .await
+        .wrap_err("Updating blog post failed")
+}
+
+/// All markdown files in `dir` and its subdirectories, skipping hidden ones like `.git`.
+fn markdown_files(dir: &Path) -> color_eyre::Result<Vec<PathBuf>> {
+    let mut md_files = Vec::new();
+
+    for entry in std::fs::read_dir(dir)
+        .wrap_err_with(|| format!("Reading directory {} failed", dir.display()))?
+    {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        {
+            continue;
+        }
+
+        if path.is_dir() {
+            md_files.extend(markdown_files(&path)?);
+        } else if path.extension().is_some_and(|extension| extension == "md") {
+            md_files.push(path);
+        }
+    }
+
+    md_files.sort();
+    Ok(md_files)
+}
+
+/// Whether uploading `local` again would leave `stored` as it is, apart from the update time.
+fn is_same_post(stored: &BlogPost, local: &BlogPost) -> bool {
+    let normalized = BlogPost {
+        updated_at: stored.updated_at,
+        tags: local.tags.iter().cloned().sorted().collect(),
+        ..local.clone()
+    };
+
+    &normalized == stored
+}
+
+struct LocalPost {
+    md_file: PathBuf,
+    post: BlogPost,
+    media_assets: Vec<MediaAsset>,
+}
+
+/// Where `export` writes author profiles, which `sync` skips since they are not posts.
+const AUTHORS_DIR: &str = "authors";
+
+/// Reads the posts in `dir` for `sync`, which checks that every url is used only once.
+fn local_posts(dir: &Path) -> color_eyre::Result<Vec<LocalPost>> {
+    let authors_dir = dir.join(AUTHORS_DIR);
+
+    let mut local_posts = Vec::new();
+    for md_file in markdown_files(dir)? {
+        if md_file.starts_with(&authors_dir) {
+            continue;
+        }
+
+        let (post, media_assets) = blog_post_from_md_file(&md_file)
+            .wrap_err_with(|| format!("Reading {} failed", md_file.display()))?;
+
+        local_posts.push(LocalPost {
+            md_file,
+            post,
+            media_assets,
+        });
+    }
+
+    if let Some(url) = local_posts
+        .iter()
+        .map(|local_post| &local_post.post.url)
+        .duplicates()
+        .next()
+    {
+        return Err(eyre!("Several files have the url {url}"));
+    }
+
+    Ok(local_posts)
+}
+
+/// Makes the posts in the database match the markdown files in `dir`, matching them by url.
+async fn sync(dir: &Path, prune: bool, changed_by: Option<&Author>) -> color_eyre::Result<()> {
+    let local_posts = local_posts(dir)?;
+
+    let database = connect_postgres().await?;
+
+    let mut transaction = database.begin().await?;
+
+    let mut stored_posts: HashMap<_, _> = database::get_blog_posts(
+        None,
+        None,
+        None,
+        false,
+        PostSelection::All,
+        &mut *transaction,
+    )
+    .await?
+    .into_iter()
+    .map(|post| (post.url.clone(), post))
+    .collect();
+
+    let mut created = Vec::new();
+    let mut updated = Vec::new();
+    for local_post in local_posts {
+        match stored_posts.remove(&local_post.post.url) {
+            None => created.push(local_post),
+            Some(stored_post) if !is_same_post(&stored_post, &local_post.post) => {
+                updated.push((stored_post, local_post));
+            }
+            Some(_) => (),
+        }
+    }
+    let orphaned = stored_posts
+        .into_values()
+        .sorted_by(|a, b| a.url.cmp(&b.url))
+        .collect_vec();
+
+    for LocalPost { md_file, post, .. } in &created {
+        println!(
+            "Create {} ({}) from {}",
+            post.url,
+            post.status,
+            md_file.display()
+        );
+    }
+    for (stored_post, LocalPost { md_file, post, .. }) in &updated {
+        println!("Update {} from {}:", post.url, md_file.display());
+        println!();
+        print_blog_post_diff(stored_post, post);
+    }
+    for post in &orphaned {
+        if prune {
+            println!("Delete {} ({})", post.url, post.status);
+        } else {
+            println!(
+                "Keep {} ({}), which has no markdown file; use --prune to delete it",
+                post.url, post.status
+            );
+        }
+    }
+
+    if created.is_empty() && updated.is_empty() && (orphaned.is_empty() || !prune) {
+        println!("Nothing to change");
+        return Ok(());
+    }
+
+    if !cli_io::prompt("Apply these changes?").wrap_err("Prompting user failed")? {
+        return Err(eyre!("User aborted"));
+    }
+
+    // Deleting first frees up urls, series parts and translations for the other changes
+    if prune {
+        for post in &orphaned {
+            database::delete_blog_post(&post.url, &mut transaction)
+                .await
+                .wrap_err_with(|| format!("Deleting {} failed", post.url))?;
+        }
+    }
+
+    for (_, local_post) in &updated {
+        let post = &local_post.post;
+        let changed_by = changed_by.map_or_else(|| main_author(post), Ok)?;
+
+        for media_asset in &local_post.media_assets {
+            database::insert_media_asset(media_asset, &mut transaction).await?;
+        }
+        database::update_blog_post(None, post, changed_by, &mut transaction)
+            .await
+            .wrap_err_with(|| format!("Updating {} failed", post.url))?;
+    }
+
+    for local_post in &created {
+        let post = &local_post.post;
+
+        for media_asset in &local_post.media_assets {
+            database::insert_media_asset(media_asset, &mut transaction).await?;
+        }
+        database::insert_blog_post(post, &mut transaction)
+            .await
+            .wrap_err_with(|| format!("Inserting {} failed", post.url))?;
+    }
+
+    transaction
+        .commit()
+        .await
+        .wrap_err("Applying changes failed")
+}
+
+/// Writes every post with its images to a markdown file that `upload-blog-post` accepts.
+/// Posts go into `posts` and author profiles into `authors`, which `sync` skips, so the whole
+/// export can be synced again.
+async fn export(database: &dyn BlogStore, output_dir: &Path) -> color_eyre::Result<()> {
+    let authors_dir = output_dir.join(AUTHORS_DIR);
+    std::fs::create_dir_all(&authors_dir).wrap_err("Creating authors directory failed")?;
+
+    for author_profile in database.get_authors().await? {
+        let author = &author_profile.author;
+        if !is_plain_relative_path(&author.0) {
+            return Err(eyre!("Author {author} is not a valid file name"));
+        }
+
+        let front_matter = serde_json::to_string_pretty(&AuthorFrontMatter::from(&author_profile))
+            .wrap_err("Serializing front matter failed")?;
+        let bio = author_profile
+            .bio_markdown
+            .as_deref()
+            .map(|bio| strip_metadata_block(bio, md_options()))
+            .unwrap_or_default();
+        let contents = format!("+++\n{front_matter}\n+++\n{bio}");
+
+        let md_file = authors_dir.join(format!("{author}.md"));
+        std::fs::write(&md_file, contents)
+            .wrap_err_with(|| format!("Writing {} failed", md_file.display()))?;
+
+        println!("Exported {}", md_file.display());
+    }
+
+    let posts_dir = output_dir.join("posts");
+    std::fs::create_dir_all(&posts_dir).wrap_err("Creating posts directory failed")?;
+
+    let mut posts_without_markdown = Vec::new();
+    for post in database
+        .get_blog_posts(None, None, None, false, PostSelection::All)
+        .await?
+    {
+        let Some(markdown) = &post.markdown else {
+            posts_without_markdown.push(post.url);
+            continue;
+        };
+
+        if !is_plain_relative_path(&post.url) {
+            return Err(eyre!("Post url {} is not a valid file name", post.url));
+        }
+
+        let mut front_matter = FrontMatter::from(&post);
+        // Without a reading time in the front matter, the estimate is used again on upload
+        let estimate = render_blog_post_md(markdown, &post.image_urls)?
+            .text_stats
+            .reading_time();
+        if post.reading_time == estimate {
+            front_matter.reading_time_minutes = None;
+        }
+
+        let front_matter = serde_json::to_string_pretty(&front_matter)
+            .wrap_err("Serializing front matter failed")?;
+        let contents = format!(
+            "+++\n{front_matter}\n+++\n{}",
+            strip_metadata_block(markdown, md_options())
+        );
+
+        // Images are read relative to the markdown file when the post is uploaded again
+        let post_dir = posts_dir.join(&post.url);
+        std::fs::create_dir_all(&post_dir)
+            .wrap_err_with(|| format!("Creating {} failed", post_dir.display()))?;
 
-    let full_post = full_blog_post_from_md(markdown)?;
+        let md_file = post_dir.join(format!("{}.md", post.url));
+        std::fs::write(&md_file, contents)
+            .wrap_err_with(|| format!("Writing {} failed", md_file.display()))?;
+
+        for (image_path, media_path) in &post.image_urls {
+            if !is_plain_relative_path(image_path) {
+                return Err(eyre!(
+                    "Image {image_path} of {} would be written outside of its post directory",
+                    post.url
+                ));
+            }
+
+            let media_asset = match hash_from_path(media_path) {
+                Some(hash) => database.get_media_asset(hash).await?,
+                None => None,
+            }
+            .ok_or_else(|| eyre!("Media asset {media_path} of {} not found", post.url))?;
+
+            let image_file = post_dir.join(image_path);
+            if let Some(parent) = image_file.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&image_file, media_asset.data)
+                .wrap_err_with(|| format!("Writing {} failed", image_file.display()))?;
+        }
+
+        println!("Exported {}", md_file.display());
+    }
+
+    if !posts_without_markdown.is_empty() {
+        println!(
+            "Posts without markdown could not be exported: {}",
+            posts_without_markdown.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Whether `path` stays inside the directory it is joined to.
+fn is_plain_relative_path(path: &str) -> bool {
+    Path::new(path)
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
 
+/// Renders every post again whose html is from an older [`RENDERER_VERSION`].
+async fn rerender(dry_run: bool) -> color_eyre::Result<()> {
     let database = connect_database().await?;
 
-    let old_full_post =
-        database::get_blog_post(original_url.unwrap_or(&full_post.url), false, &database)
-            .await?
-            .ok_or_eyre("Post with original url not found")?;
+    let mut rerendered_posts = Vec::new();
+    for post in database
+        .get_blog_posts(None, None, None, false, PostSelection::All)
+        .await?
+    {
+        if post.renderer_version >= RENDERER_VERSION {
+            continue;
+        }
 
-    if let Some(old_full_post_md) = old_full_post.markdown {
-        println!("Markdown Diff:");
+        let Some(markdown) = &post.markdown else {
+            println!("Skipping {}: it has no markdown", post.url);
+            continue;
+        };
+
+        // Posts uploaded before image urls were stored need their image files
+        let missing_images = local_image_paths(markdown, md_options())
+            .into_iter()
+            .filter(|path| !post.image_urls.contains_key(path))
+            .collect_vec();
+        if !missing_images.is_empty() {
+            println!(
+                "Skipping {}: unknown urls for images {}, update it from its markdown file instead",
+                post.url,
+                missing_images.join(", "),
+            );
+            continue;
+        }
+
+        let RenderResult {
+            metadata,
+            html,
+            outline,
+            text_stats,
+            excerpt,
+            plain_text,
+        } = render_blog_post_md(markdown, &post.image_urls)?;
+
+        let Some(front_matter) =
+            metadata.and_then(|metadata| serde_json::from_str::<FrontMatter>(&metadata).ok())
+        else {
+            println!(
+                "Skipping {}: its front matter could not be parsed, update it from its markdown file instead",
+                post.url,
+            );
+            continue;
+        };
+
+        println!(
+            "{} (renderer version {} -> {RENDERER_VERSION}):",
+            post.url, post.renderer_version,
+        );
         println!();
-        diff::print_diff(&old_full_post_md, full_post.markdown.as_ref().unwrap());
+        diff::print_diff(&post.html, &html);
         println!();
+
+        // Only a reading time set in the front matter survives, estimates change with the renderer
+        let reading_time = front_matter
+            .reading_time_minutes
+            .map(|minutes| Duration::minutes(minutes as i64))
+            .unwrap_or_else(|| text_stats.reading_time());
+
+        rerendered_posts.push(BlogPost {
+            html,
+            outline,
+            reading_time,
+            word_count: text_stats.words,
+            excerpt,
+            plain_text,
+            renderer_version: RENDERER_VERSION,
+            ..post
+        });
     }
 
-    println!("HTML Diff:");
+    if rerendered_posts.is_empty() {
+        println!("All posts are up to date");
+        return Ok(());
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+
+    let prompt = format!("Re-render {} posts?", rerendered_posts.len());
+    if !cli_io::prompt(&prompt).wrap_err("Prompting user failed")? {
+        return Err(eyre!("User aborted"));
+    }
+
+    // The contents stay the same, so the posts don't count as updated
+    for post in &rerendered_posts {
+        database
+            .update_rendered_blog_post(post)
+            .await
+            .wrap_err_with(|| format!("Updating {} failed", post.url))?;
+    }
+
+    Ok(())
+}
+
+async fn list_revisions(url: &str) -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    let revisions = database::get_blog_post_revisions(url, &database).await?;
+
+    if revisions.is_empty() {
+        return Err(eyre!("No revisions found for post '{url}'"));
+    }
+
+    for BlogPostRevision {
+        revision,
+        post,
+        changed_at,
+        changed_by,
+        ..
+    } in revisions
+    {
+        println!(
+            "{revision}: {} by {changed_by} - '{}' ({})",
+            changed_at.format("%Y-%m-%d %H:%M:%S"),
+            post.title,
+            post.url,
+        );
+    }
+
+    Ok(())
+}
+
+async fn diff_revisions(old: i64, new: Option<i64>) -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    let old_revision = database::get_blog_post_revision(old, &database)
+        .await?
+        .ok_or_eyre("Old revision not found")?;
+
+    let new_post = if let Some(new) = new {
+        database::get_blog_post_revision(new, &database)
+            .await?
+            .ok_or_eyre("New revision not found")?
+            .post
+    } else {
+        let post_url = old_revision
+            .post_url
+            .as_deref()
+            .ok_or_eyre("Post of old revision was deleted")?;
+        database::get_blog_post(post_url, false, &database)
+            .await?
+            .ok_or_eyre("Post of old revision not found")?
+    };
+
+    print_blog_post_diff(&old_revision.post, &new_post);
+
+    Ok(())
+}
+
+async fn rollback_blog_post(
+    url: &str,
+    revision: i64,
+    changed_by: Option<&Author>,
+) -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    let mut transaction = database.begin().await?;
+
+    let current_post = database::get_blog_post(url, false, &mut *transaction)
+        .await?
+        .ok_or_eyre("Post not found")?;
+
+    let BlogPostRevision { post_url, post, .. } =
+        database::get_blog_post_revision(revision, &mut *transaction)
+            .await?
+            .ok_or_eyre("Revision not found")?;
+
+    if post_url.as_deref() != Some(url) {
+        return Err(eyre!("Revision {revision} does not belong to post '{url}'"));
+    }
+
+    print_blog_post_diff(&current_post, &post);
+
+    if !cli_io::prompt("Continue with rollback?").wrap_err("Prompting user failed")? {
+        return Err(eyre!("User aborted"));
+    }
+
+    let changed_by = changed_by.map_or_else(|| main_author(&current_post), Ok)?;
+
+    database::rollback_blog_post(url, revision, changed_by, &mut transaction)
+        .await
+        .wrap_err("Rolling back blog post failed before transaction commit")?;
+
+    transaction
+        .commit()
+        .await
+        .wrap_err("Rolling back blog post failed")
+}
+
+async fn delete_blog_post(url: &str) -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    let mut transaction = database.begin().await?;
+
+    let post = database::get_blog_post(url, false, &mut *transaction)
+        .await?
+        .ok_or_eyre("Post not found")?;
+
+    if !cli_io::prompt(&format!(
+        "Permanently delete '{}' ({url}) including its tags? Its revision history is kept.",
+        post.title,
+    ))
+    .wrap_err("Prompting user failed")?
+    {
+        return Err(eyre!("User aborted"));
+    }
+
+    database::delete_blog_post(url, &mut transaction)
+        .await
+        .wrap_err("Deleting blog post failed before transaction commit")?;
+
+    transaction
+        .commit()
+        .await
+        .wrap_err("Deleting blog post failed")
+}
+
+async fn archive_blog_post(
+    url: &str,
+    archived: bool,
+    changed_by: Option<&Author>,
+) -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    let mut transaction = database.begin().await?;
+
+    let post = database::get_blog_post(url, false, &mut *transaction)
+        .await?
+        .ok_or_eyre("Post not found")?;
+
+    let action = if archived { "Archive" } else { "Unarchive" };
+    if !cli_io::prompt(&format!("{action} '{}' ({url})?", post.title))
+        .wrap_err("Prompting user failed")?
+    {
+        return Err(eyre!("User aborted"));
+    }
+
+    let changed_by = changed_by.map_or_else(|| main_author(&post), Ok)?;
+
+    database::archive_blog_post(url, archived, changed_by, &mut transaction)
+        .await
+        .wrap_err("Archiving blog post failed before transaction commit")?;
+
+    transaction
+        .commit()
+        .await
+        .wrap_err("Archiving blog post failed")
+}
+
+async fn unpublish_blog_post(url: &str, changed_by: Option<&Author>) -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    let mut transaction = database.begin().await?;
+
+    let post = database::get_blog_post(url, false, &mut *transaction)
+        .await?
+        .ok_or_eyre("Post not found")?;
+
+    if !cli_io::prompt(&format!(
+        "Unpublish '{}' ({url})? It will be turned back into a draft.",
+        post.title,
+    ))
+    .wrap_err("Prompting user failed")?
+    {
+        return Err(eyre!("User aborted"));
+    }
+
+    let changed_by = changed_by.map_or_else(|| main_author(&post), Ok)?;
+
+    database::unpublish_blog_post(url, changed_by, &mut transaction)
+        .await
+        .wrap_err("Unpublishing blog post failed before transaction commit")?;
+
+    transaction
+        .commit()
+        .await
+        .wrap_err("Unpublishing blog post failed")
+}
+
+fn print_preview_token(
+    PreviewToken {
+        token,
+        post_url,
+        created_at,
+        created_by,
+    }: &PreviewToken,
+) {
+    println!(
+        "{token}: /blog/{post_url}?preview={token} ({} by {created_by})",
+        created_at.format("%Y-%m-%d %H:%M:%S"),
+    );
+}
+
+async fn create_preview_token(url: &str, created_by: Option<&Author>) -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    let mut transaction = database.begin().await?;
+
+    let post = database::get_blog_post(url, false, &mut *transaction)
+        .await?
+        .ok_or_eyre("Post not found")?;
+
+    if post.is_public() {
+        return Err(eyre!("Post '{url}' is already public"));
+    }
+
+    let created_by = created_by.map_or_else(|| main_author(&post), Ok)?;
+
+    let preview_token = database::create_preview_token(url, created_by, &mut transaction)
+        .await
+        .wrap_err("Creating preview token failed before transaction commit")?;
+
+    transaction
+        .commit()
+        .await
+        .wrap_err("Creating preview token failed")?;
+
+    print_preview_token(&preview_token);
+
+    Ok(())
+}
+
+async fn list_preview_tokens(url: &str) -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    for preview_token in database::get_preview_tokens(url, &database).await? {
+        print_preview_token(&preview_token);
+    }
+
+    Ok(())
+}
+
+async fn revoke_preview_token(token: &str) -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    let mut transaction = database.begin().await?;
+    database::revoke_preview_token(token, &mut transaction)
+        .await
+        .wrap_err("Revoking preview token failed before transaction commit")?;
+
+    transaction
+        .commit()
+        .await
+        .wrap_err("Revoking preview token failed")
+}
+
+async fn upload_author(md_file: &Path) -> color_eyre::Result<()> {
+    let markdown = std::fs::read_to_string(md_file)?;
+
+    let author_profile = author_profile_from_md(markdown)?;
+
+    let database = connect_postgres().await?;
+
+    let mut transaction = database.begin().await?;
+    database::insert_author(&author_profile, &mut transaction)
+        .await
+        .wrap_err("Inserting author failed before transaction commit")?;
+
+    transaction
+        .commit()
+        .await
+        .wrap_err("Inserting author failed")
+}
+
+async fn update_author(md_file: &Path) -> color_eyre::Result<()> {
+    let markdown = std::fs::read_to_string(md_file)?;
+
+    let author_profile = author_profile_from_md(markdown)?;
+
+    let database = connect_postgres().await?;
+
+    let mut transaction = database.begin().await?;
+
+    let old_author_profile = database::get_author(&author_profile.author, &mut *transaction)
+        .await?
+        .ok_or_eyre("Author not found")?;
+
+    println!("Bio Diff:");
     println!();
-    diff::print_diff(&old_full_post.html, &full_post.html);
+    diff::print_diff(
+        old_author_profile
+            .bio_markdown
+            .as_deref()
+            .unwrap_or_default(),
+        author_profile.bio_markdown.as_deref().unwrap_or_default(),
+    );
     println!();
 
     if !cli_io::prompt("Continue with update?").wrap_err("Prompting user failed")? {
         return Err(eyre!("User aborted"));
     }
 
-    let mut transaction = database.begin().await?;
-    database::update_blog_post(original_url, &full_post, new_author, &mut transaction).await?;
+    database::update_author(&author_profile, &mut transaction)
+        .await
+        .wrap_err("Updating author failed before transaction commit")?;
 
     transaction
         .commit()
         .await
-        .wrap_err("Updating blog post failed")
+        .wrap_err("Updating author failed")
+}
+
+async fn list_authors() -> color_eyre::Result<()> {
+    let database = connect_postgres().await?;
+
+    for author_profile in database::get_authors(&database).await? {
+        println!("{}: {}", author_profile.author, author_profile.name());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bae_common::blog::AuthorProfile;
+    use bae_common::database::{Author, BlogStore, MemoryStore};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn export_sync_test() -> color_eyre::Result<()> {
+        // Written like `export` writes front matter, so the markdown stays the same as well
+        let markdown = r#"+++
+{
+  "url": "exported",
+  "title": "Exported",
+  "authors": [
+    "Quiet"
+  ],
+  "tags": [
+    "post"
+  ],
+  "status": "published",
+  "publication_date": "2026-10-18T00:00:00Z",
+  "language": "en-gb"
+}
++++
+Contents of *exported*
+"#;
+        let post = super::full_blog_post_from_md(markdown.to_string(), HashMap::new())?;
+
+        let store = MemoryStore::new();
+        store.insert_author(AuthorProfile {
+            author: Author("Quiet".to_string()),
+            display_name: Some("Quiet Person".to_string()),
+            bio_markdown: Some("Writes posts".to_string()),
+            bio_html: Some("<p>Writes posts</p>".to_string()),
+            avatar_path: None,
+            links: Vec::new(),
+        });
+        store.insert_blog_post(&post, &[]).await?;
+
+        let output_dir = std::env::temp_dir().join(format!("bae-export-{}", std::process::id()));
+        super::export(&store, &output_dir).await?;
+        let local_posts = super::local_posts(&output_dir);
+        let author_file = output_dir.join("authors").join("Quiet.md").is_file();
+        std::fs::remove_dir_all(&output_dir)?;
+
+        // Syncing the export again reads the posts only, and leaves them as they are
+        assert!(author_file);
+        let stored = store.get_blog_post("exported", false).await?.unwrap();
+        let local_posts = local_posts?;
+        assert_eq!(local_posts.len(), 1);
+        assert!(super::is_same_post(&stored, &local_posts[0].post));
+
+        Ok(())
+    }
 }
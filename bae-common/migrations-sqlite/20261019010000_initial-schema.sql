@@ -0,0 +1,75 @@
+-- Mirrors the parts of the Postgres schema the SQLite backend supports.
+-- Timestamps are stored as RFC 3339 text.
+create table blog_post
+(
+    url                  text    not null
+        constraint blog_post_pk
+            primary key,
+    title                text    not null,
+    description          text    not null,
+    markdown             text,
+    html                 text    not null,
+    reading_time_minutes integer not null,
+    status               text    not null
+        constraint blog_post_status_check
+            check (status in ('draft', 'unlisted', 'scheduled', 'published', 'archived')),
+    publication_date     text,
+    updated_at           text    not null,
+    series               text,
+    series_part          integer,
+    language             text    not null,
+    translation_key      text,
+    constraint blog_post_publication_date_check
+        check (status in ('draft', 'unlisted') or publication_date is not null),
+    constraint blog_post_series_check
+        check ((series is null) = (series_part is null) and (series_part is null or series_part > 0)),
+    constraint blog_post_series_part_unique
+        unique (series, series_part),
+    constraint blog_post_translation_unique
+        unique (translation_key, language)
+);
+
+create table tag
+(
+    tag text not null,
+    url text not null
+        constraint tag_blog_post_fk
+            references blog_post
+            on update cascade on delete cascade,
+    constraint tag_pk
+        primary key (tag, url)
+);
+
+create table post_author
+(
+    url      text    not null
+        constraint post_author_blog_post_fk
+            references blog_post
+            on update cascade on delete cascade,
+    author   text    not null,
+    position integer not null,
+    constraint post_author_pk
+        primary key (url, author),
+    constraint post_author_position_unique
+        unique (url, position)
+);
+
+create table url_redirect
+(
+    old_url text not null
+        constraint url_redirect_pk
+            primary key,
+    new_url text not null
+        constraint url_redirect_blog_post_fk
+            references blog_post
+            on update cascade on delete cascade
+);
+
+create table media_asset
+(
+    hash         text not null
+        constraint media_asset_pk
+            primary key,
+    content_type text not null,
+    data         blob not null
+);
@@ -0,0 +1,6 @@
+-- Posts rendered before versions were tracked are treated as outdated
+alter table blog_post
+    add renderer_version integer not null default 0;
+
+alter table blog_post
+    add image_urls text not null default '{}';
@@ -0,0 +1,5 @@
+alter table blog_post
+    add pinned boolean not null default false;
+
+alter table blog_post
+    add featured boolean not null default false;
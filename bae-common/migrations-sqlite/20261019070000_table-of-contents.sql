@@ -0,0 +1,6 @@
+-- Posts rendered before outlines were stored get theirs on the next rerender
+alter table blog_post
+    add outline text not null default '[]';
+
+alter table blog_post
+    add table_of_contents boolean not null default false;
@@ -0,0 +1,4 @@
+-- An empty description means the post has none and its excerpt is shown instead.
+-- Posts rendered before excerpts were stored get theirs on the next rerender
+alter table blog_post
+    add excerpt text not null default '';
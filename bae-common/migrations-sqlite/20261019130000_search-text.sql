@@ -0,0 +1,3 @@
+-- Posts rendered before their plain text was stored get it on the next rerender
+alter table blog_post
+    add plain_text text not null default '';
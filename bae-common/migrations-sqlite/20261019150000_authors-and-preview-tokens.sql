@@ -0,0 +1,40 @@
+-- Posts may name authors without a profile, so post_author does not reference author.
+create table author
+(
+    author       text not null
+        constraint author_pk
+            primary key,
+    display_name text,
+    bio_markdown text,
+    bio_html     text,
+    avatar_path  text
+);
+
+create table author_link
+(
+    author   text    not null
+        constraint author_link_author_fk
+            references author
+            on update cascade on delete cascade,
+    position integer not null,
+    label    text    not null,
+    url      text    not null,
+    constraint author_link_pk
+        primary key (author, position)
+);
+
+create table preview_token
+(
+    token      text not null
+        constraint preview_token_pk
+            primary key,
+    post_url   text not null
+        constraint preview_token_blog_post_fk
+            references blog_post
+            on update cascade on delete cascade,
+    created_at text not null,
+    created_by text not null
+);
+
+create index preview_token_post_url_index
+    on preview_token (post_url);
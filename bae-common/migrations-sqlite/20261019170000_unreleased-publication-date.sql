@@ -0,0 +1,20 @@
+-- SQLite can't change the check constraint of an existing table, so the new rule is enforced by triggers.
+update blog_post
+set publication_date = null
+where status in ('draft', 'unlisted');
+
+create trigger blog_post_unreleased_publication_date_insert
+    before insert
+    on blog_post
+    when new.status in ('draft', 'unlisted') and new.publication_date is not null
+begin
+    select raise(abort, 'blog_post_status_check');
+end;
+
+create trigger blog_post_unreleased_publication_date_update
+    before update
+    on blog_post
+    when new.status in ('draft', 'unlisted') and new.publication_date is not null
+begin
+    select raise(abort, 'blog_post_status_check');
+end;
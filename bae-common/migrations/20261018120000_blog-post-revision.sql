@@ -0,0 +1,43 @@
+create table blog_post_revision
+(
+    revision             bigserial                not null
+        constraint blog_post_revision_pk
+            primary key,
+    post_url             text                     not null
+        constraint blog_post_revision_blog_post_fk
+            references blog_post
+            on update cascade on delete cascade,
+    url                  text                     not null,
+    title                text                     not null,
+    description          text                     not null,
+    author               text                     not null,
+    markdown             text,
+    html                 text                     not null,
+    tags                 text[]                   not null,
+    reading_time_minutes integer                  not null,
+    accessible           boolean                  not null,
+    publication_date     timestamp with time zone,
+    changed_at           timestamp with time zone default now() not null,
+    changed_by           text                     not null
+);
+
+create index blog_post_revision_post_url_index
+    on blog_post_revision (post_url);
+
+-- Snapshot the current state of every existing post as its first revision
+insert into blog_post_revision
+    (post_url, url, title, description, author, markdown, html, tags, reading_time_minutes,
+     accessible, publication_date, changed_by)
+select url,
+       url,
+       title,
+       description,
+       author,
+       markdown,
+       html,
+       array(select tag from tag where tag.url = blog_post.url order by tag),
+       reading_time_minutes,
+       accessible,
+       publication_date,
+       author
+from blog_post;
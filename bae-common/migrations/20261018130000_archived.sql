@@ -0,0 +1,5 @@
+alter table blog_post
+    add archived boolean default false not null;
+
+alter table blog_post_revision
+    add archived boolean default false not null;
@@ -0,0 +1,11 @@
+create table url_redirect
+(
+    old_url text not null
+        constraint url_redirect_pk
+            primary key,
+    -- Cascading updates keep chains of several renames pointing at the current url
+    new_url text not null
+        constraint url_redirect_blog_post_fk
+            references blog_post
+            on update cascade on delete cascade
+);
@@ -0,0 +1,11 @@
+alter table blog_post
+    add series text,
+    add series_part integer,
+    add constraint blog_post_series_check
+        check ((series is null) = (series_part is null) and (series_part is null or series_part > 0)),
+    add constraint blog_post_series_part_unique
+        unique (series, series_part);
+
+alter table blog_post_revision
+    add series text,
+    add series_part integer;
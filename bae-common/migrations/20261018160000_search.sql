@@ -0,0 +1,9 @@
+alter table blog_post
+    add search_vector tsvector generated always as (
+        setweight(to_tsvector('english', title), 'A') ||
+        setweight(to_tsvector('english', description), 'B') ||
+        setweight(to_tsvector('english', coalesce(markdown, '')), 'C')
+        ) stored;
+
+create index blog_post_search_vector_index
+    on blog_post using gin (search_vector);
@@ -0,0 +1,18 @@
+alter table author
+    add display_name text,
+    add bio_markdown text,
+    add bio_html     text,
+    add avatar_path  text;
+
+create table author_link
+(
+    author   text    not null
+        constraint author_link_author_fk
+            references author
+            on update cascade on delete cascade,
+    position integer not null,
+    label    text    not null,
+    url      text    not null,
+    constraint author_link_pk
+        primary key (author, position)
+);
@@ -0,0 +1,6 @@
+-- Follow url changes of the blog post instead of blocking them
+alter table tag
+    drop constraint blog_post_fk,
+    add constraint blog_post_fk
+        foreign key (url) references blog_post
+            on update cascade;
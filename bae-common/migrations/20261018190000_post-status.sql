@@ -0,0 +1,53 @@
+create type post_status as enum ('draft', 'unlisted', 'scheduled', 'published', 'archived');
+
+alter table blog_post
+    add status post_status;
+
+alter table blog_post_revision
+    add status post_status;
+
+-- Posts with a publication date keep being published at that date. Note that scheduled posts are no longer
+-- accessible before their publication date, even if they previously were.
+update blog_post
+set status = case
+                 when publication_date is null and accessible then 'unlisted'
+                 when publication_date is null then 'draft'
+                 when publication_date > now() then 'scheduled'
+                 when archived then 'archived'
+                 else 'published'
+    end::post_status;
+
+update blog_post_revision
+set status = case
+                 when publication_date is null and accessible then 'unlisted'
+                 when publication_date is null then 'draft'
+                 when publication_date > now() then 'scheduled'
+                 when archived then 'archived'
+                 else 'published'
+    end::post_status;
+
+alter table blog_post
+    alter status set not null,
+    drop accessible,
+    drop archived,
+    add constraint blog_post_status_check
+        check (status in ('draft', 'unlisted') or publication_date is not null);
+
+alter table blog_post_revision
+    alter status set not null,
+    drop accessible,
+    drop archived;
+
+-- The visibility rules, shared by all queries
+
+-- Whether the post is listed and accessible to everyone
+create function post_is_public(status post_status, publication_date timestamp with time zone) returns boolean
+    language sql
+    stable
+return status in ('scheduled', 'published', 'archived') and publication_date <= now();
+
+-- Whether the post is accessible to everyone knowing its url
+create function post_is_accessible(status post_status, publication_date timestamp with time zone) returns boolean
+    language sql
+    stable
+return status = 'unlisted' or post_is_public(status, publication_date);
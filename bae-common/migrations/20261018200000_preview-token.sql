@@ -0,0 +1,15 @@
+create table preview_token
+(
+    token      text                                   not null
+        constraint preview_token_pk
+            primary key,
+    post_url   text                                   not null
+        constraint preview_token_blog_post_fk
+            references blog_post
+            on update cascade on delete cascade,
+    created_at timestamp with time zone default now() not null,
+    created_by text                                   not null
+);
+
+create index preview_token_post_url_index
+    on preview_token (post_url);
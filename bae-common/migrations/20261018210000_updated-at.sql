@@ -0,0 +1,21 @@
+alter table blog_post
+    add updated_at timestamp with time zone;
+
+alter table blog_post_revision
+    add updated_at timestamp with time zone;
+
+update blog_post_revision
+set updated_at = changed_at;
+
+-- The latest revision is the best guess for existing posts
+update blog_post
+set updated_at = coalesce((select max(changed_at)
+                           from blog_post_revision
+                           where blog_post_revision.post_url = blog_post.url),
+                          now());
+
+alter table blog_post
+    alter updated_at set not null;
+
+alter table blog_post_revision
+    alter updated_at set not null;
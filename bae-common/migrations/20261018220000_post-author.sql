@@ -0,0 +1,33 @@
+create table post_author
+(
+    url      text    not null
+        constraint post_author_blog_post_fk
+            references blog_post
+            on update cascade on delete cascade,
+    author   text    not null
+        constraint post_author_author_fk
+            references author
+            on update cascade,
+    position integer not null,
+    constraint post_author_pk
+        primary key (url, author),
+    constraint post_author_position_unique
+        unique (url, position)
+);
+
+insert into post_author (url, author, position)
+select url, author, 0
+from blog_post;
+
+alter table blog_post
+    drop author;
+
+alter table blog_post_revision
+    add authors text[];
+
+update blog_post_revision
+set authors = array [author];
+
+alter table blog_post_revision
+    alter authors set not null,
+    drop author;
@@ -0,0 +1,16 @@
+-- Everything so far has been written in British English
+alter table blog_post
+    add language        text not null default 'en-gb',
+    add translation_key text,
+    add constraint blog_post_translation_unique
+        unique (translation_key, language);
+
+alter table blog_post
+    alter language drop default;
+
+alter table blog_post_revision
+    add language        text not null default 'en-gb',
+    add translation_key text;
+
+alter table blog_post_revision
+    alter language drop default;
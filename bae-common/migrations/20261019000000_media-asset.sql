@@ -0,0 +1,9 @@
+create table media_asset
+(
+    hash         text                                   not null
+        constraint media_asset_pk
+            primary key,
+    content_type text                                   not null,
+    data         bytea                                  not null,
+    created_at   timestamp with time zone default now() not null
+);
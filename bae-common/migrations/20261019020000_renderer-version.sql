@@ -0,0 +1,8 @@
+-- Posts rendered before versions were tracked are treated as outdated
+alter table blog_post
+    add renderer_version integer not null default 0,
+    add image_urls       jsonb   not null default '{}';
+
+alter table blog_post_revision
+    add renderer_version integer not null default 0,
+    add image_urls       jsonb   not null default '{}';
@@ -0,0 +1,7 @@
+alter table blog_post
+    add pinned   boolean not null default false,
+    add featured boolean not null default false;
+
+alter table blog_post_revision
+    add pinned   boolean not null default false,
+    add featured boolean not null default false;
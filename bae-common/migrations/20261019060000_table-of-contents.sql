@@ -0,0 +1,8 @@
+-- Posts rendered before outlines were stored get theirs on the next rerender
+alter table blog_post
+    add outline           jsonb   not null default '[]',
+    add table_of_contents boolean not null default false;
+
+alter table blog_post_revision
+    add outline           jsonb   not null default '[]',
+    add table_of_contents boolean not null default false;
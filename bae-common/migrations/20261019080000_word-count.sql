@@ -0,0 +1,6 @@
+-- Posts rendered before word counts were stored get theirs on the next rerender
+alter table blog_post
+    add word_count integer not null default 0;
+
+alter table blog_post_revision
+    add word_count integer not null default 0;
@@ -0,0 +1,18 @@
+-- An empty description means the post has none and its excerpt is shown instead
+alter table blog_post
+    alter description drop default;
+
+update blog_post
+set description = ''
+where description = 'No description';
+
+update blog_post_revision
+set description = ''
+where description = 'No description';
+
+-- Posts rendered before excerpts were stored get theirs on the next rerender
+alter table blog_post
+    add excerpt text not null default '';
+
+alter table blog_post_revision
+    add excerpt text not null default '';
@@ -0,0 +1,31 @@
+-- Posts rendered before their plain text was stored get it on the next rerender, until then only
+-- their title and description are searched
+alter table blog_post
+    add plain_text text not null default '';
+
+alter table blog_post_revision
+    add plain_text text not null default '';
+
+-- Stemming only works for the language it was made for, other posts are matched word by word
+create function search_config(language text) returns regconfig
+    language sql
+    immutable
+return case
+           when language = 'en' or language like 'en-%' then 'english'::regconfig
+           else 'simple'::regconfig
+    end;
+
+drop index blog_post_search_vector_index;
+
+alter table blog_post
+    drop search_vector;
+
+alter table blog_post
+    add search_vector tsvector generated always as (
+        setweight(to_tsvector(search_config(language), title), 'A') ||
+        setweight(to_tsvector(search_config(language), description), 'B') ||
+        setweight(to_tsvector(search_config(language), plain_text), 'C')
+        ) stored;
+
+create index blog_post_search_vector_index
+    on blog_post using gin (search_vector);
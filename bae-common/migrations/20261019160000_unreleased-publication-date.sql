@@ -0,0 +1,9 @@
+-- Drafts and unlisted posts are never public, a publication date would only be misleading
+update blog_post
+set publication_date = null
+where status in ('draft', 'unlisted');
+
+alter table blog_post
+    drop constraint blog_post_status_check,
+    add constraint blog_post_status_check
+        check ((status in ('draft', 'unlisted')) = (publication_date is null));
@@ -0,0 +1,9 @@
+-- Changes before update times were tracked were guessed from the revisions, which also count
+-- re-renders and status changes. The publication date is the better guess where there is one.
+update blog_post
+set updated_at = coalesce(publication_date, updated_at)
+where updated_at <= (select installed_on from _sqlx_migrations where version = 20261018210000);
+
+update blog_post_revision
+set updated_at = coalesce(publication_date, updated_at)
+where changed_at <= (select installed_on from _sqlx_migrations where version = 20261018210000);
@@ -0,0 +1,7 @@
+-- Deleting a post keeps its history, the revisions only lose the link to the post
+alter table blog_post_revision
+    alter post_url drop not null,
+    drop constraint blog_post_revision_blog_post_fk,
+    add constraint blog_post_revision_blog_post_fk
+        foreign key (post_url) references blog_post
+            on update cascade on delete set null;
@@ -1,30 +1,196 @@
-use crate::database::{Author, Tag};
+use crate::database::{Author, Language, PostStatus, Series, Tag};
+use crate::markdown_render::Heading;
 use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct BlogPost {
     pub url: String,
     pub title: String,
-    pub description: String,
-    pub author: Author,
+    /// Posts without a description are summarized by their `excerpt`.
+    pub description: Option<String>,
+    /// The first author is the main author.
+    pub authors: Vec<Author>,
     pub markdown: Option<String>,
     pub html: String,
     pub tags: Vec<Tag>,
     pub reading_time: Duration,
-    pub accessible: bool,
+    /// Words of prose in `html`, not counting code blocks.
+    pub word_count: u32,
+    /// Html of the start of the post, see
+    /// [`RenderResult::excerpt`](crate::markdown_render::RenderResult::excerpt).
+    pub excerpt: String,
+    /// The text of `html` without markup, for full text search.
+    pub plain_text: String,
+    pub status: PostStatus,
     pub publication_date: Option<DateTime<Utc>>,
+    /// When the contents of the post were last changed.
+    pub updated_at: DateTime<Utc>,
+    pub series: Option<SeriesPart>,
+    pub language: Language,
+    /// Shared by all translations of the same article.
+    pub translation_key: Option<String>,
+    /// The [`RENDERER_VERSION`](crate::markdown_render::RENDERER_VERSION) `html` was rendered with.
+    pub renderer_version: u32,
+    /// Maps the local image paths in `markdown` to the media urls they were rendered with, so the
+    /// markdown can be rendered again without the image files.
+    pub image_urls: HashMap<String, String>,
+    /// Listed before all other posts on the blog home.
+    pub pinned: bool,
+    /// Shown in the featured sections of the blog home and the site home.
+    pub featured: bool,
+    /// The headings of `html`.
+    pub outline: Vec<Heading>,
+    /// Whether to show a table of contents built from `outline`.
+    pub table_of_contents: bool,
+}
+
+/// A post matching a search query, with an excerpt of the matching text.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SearchResult {
+    pub blog_post: BlogPost,
+    pub snippet: Vec<SnippetFragment>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SnippetFragment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SeriesPart {
+    pub series: Series,
+    pub part: u32,
+}
+
+/// A snapshot of a [`BlogPost`] as it was after being inserted or updated.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BlogPostRevision {
+    pub revision: i64,
+    /// The current url of the post this is a revision of, `None` once the post is deleted.
+    pub post_url: Option<String>,
+    pub post: BlogPost,
+    pub changed_at: DateTime<Utc>,
+    pub changed_by: Author,
+}
+
+/// A secret token that gives access to a post before it is public.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct PreviewToken {
+    pub token: String,
+    /// The current url of the post this token gives access to.
+    pub post_url: String,
+    pub created_at: DateTime<Utc>,
+    pub created_by: Author,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct AuthorProfile {
+    pub author: Author,
+    pub display_name: Option<String>,
+    pub bio_markdown: Option<String>,
+    pub bio_html: Option<String>,
+    /// Path of the avatar image relative to the static files.
+    pub avatar_path: Option<String>,
+    pub links: Vec<AuthorLink>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AuthorLink {
+    pub label: String,
+    pub url: String,
+}
+
+impl AuthorProfile {
+    pub fn name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.author.0)
+    }
+}
+
+impl PostStatus {
+    /// Whether posts with this status are public once their publication date has passed.
+    pub fn is_released(self) -> bool {
+        matches!(
+            self,
+            PostStatus::Scheduled | PostStatus::Published | PostStatus::Archived
+        )
+    }
 }
 
 impl BlogPost {
+    /// Mirrors the `post_is_public` database function.
     pub fn is_public(&self) -> bool {
-        self.publication_date
-            .map_or(false, |date| date <= Utc::now())
+        self.status.is_released() && self.publication_date.is_some_and(|date| date <= Utc::now())
     }
 
+    /// Mirrors the `post_is_accessible` database function.
     pub fn is_accessible_or_public(&self) -> bool {
-        self.accessible || self.is_public()
+        self.status == PostStatus::Unlisted || self.is_public()
+    }
+
+    pub fn is_archived(&self) -> bool {
+        self.status == PostStatus::Archived
+    }
+
+    /// Whether the post was changed on a later day than it was published.
+    pub fn is_updated_after_publication(&self) -> bool {
+        self.publication_date
+            .is_some_and(|date| self.updated_at.date_naive() > date.date_naive())
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl BlogPost {
+    /// A post with placeholder contents, for tests to adjust with struct update syntax.
+    pub fn test_post(url: &str, status: PostStatus, publication_date: Option<i64>) -> BlogPost {
+        BlogPost {
+            url: url.to_string(),
+            title: format!("Title of {url}"),
+            description: Some(format!("Description of {url}")),
+            authors: vec![Author("Quiet".to_string())],
+            markdown: Some(format!("Contents of {url}")),
+            html: format!("<p>Contents of {url}</p>"),
+            tags: vec![Tag("post".to_string())],
+            reading_time: Duration::minutes(1),
+            word_count: 3,
+            excerpt: String::new(),
+            plain_text: format!("Contents of {url}"),
+            status,
+            publication_date: publication_date
+                .map(|timestamp| DateTime::from_timestamp(timestamp, 0).unwrap()),
+            updated_at: DateTime::UNIX_EPOCH,
+            series: None,
+            language: Language("en-gb".to_string()),
+            translation_key: None,
+            renderer_version: 0,
+            image_urls: HashMap::new(),
+            pinned: false,
+            featured: false,
+            outline: Vec::new(),
+            table_of_contents: false,
+        }
     }
+
+    /// Resets `updated_at`, see [`ignoring_update_times`].
+    pub fn ignoring_update_time(self) -> BlogPost {
+        BlogPost {
+            updated_at: DateTime::UNIX_EPOCH,
+            ..self
+        }
+    }
+}
+
+/// Stores set `updated_at` to the time of every change to the contents, so it is left out when comparing posts
+/// that were written during a test.
+#[cfg(any(test, feature = "test-util"))]
+pub fn ignoring_update_times(blog_posts: impl IntoIterator<Item = BlogPost>) -> Vec<BlogPost> {
+    blog_posts
+        .into_iter()
+        .map(BlogPost::ignoring_update_time)
+        .collect()
 }
 
 impl Display for Tag {
@@ -33,6 +199,30 @@ impl Display for Tag {
     }
 }
 
+impl Display for Series {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Display for PostStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Unlisted => "unlisted",
+            PostStatus::Scheduled => "scheduled",
+            PostStatus::Published => "published",
+            PostStatus::Archived => "archived",
+        })
+    }
+}
+
 impl Display for Author {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(&self.0)
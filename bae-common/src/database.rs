@@ -1,12 +1,30 @@
-use crate::blog::BlogPost;
+use crate::blog::{
+    AuthorLink, AuthorProfile, BlogPost, BlogPostRevision, PreviewToken, SearchResult, SeriesPart,
+    SnippetFragment,
+};
+use crate::markdown_render::Heading;
+use crate::media::MediaAsset;
+use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
 use futures::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use sqlx::migrate::Migrate;
-use sqlx::{migrate, query, query_as, query_scalar, Acquire, PgExecutor, Postgres, Transaction};
+use sqlx::postgres::PgListener;
+use sqlx::types::Json;
+use sqlx::{
+    migrate, query, query_as, query_scalar, Acquire, PgExecutor, PgPool, Postgres, Transaction,
+};
+use std::collections::HashMap;
 use std::ops::Deref;
+use std::sync::Arc;
 use thiserror::Error;
 
+mod memory;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use memory::MemoryStore;
+
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 #[derive(Debug, Error)]
@@ -17,6 +35,8 @@ pub enum Error {
     UnexpectedData,
     #[error("Input was invalid")]
     InvalidInput,
+    #[error("Operation is not supported by this database backend")]
+    Unsupported,
 }
 
 #[derive(
@@ -43,17 +63,183 @@ impl From<String> for Tag {
     }
 }
 
+#[derive(
+    Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash, sqlx::Type, Serialize, Deserialize,
+)]
+#[sqlx(transparent, type_name = "text")]
+pub struct Series(pub String);
+
+impl From<String> for Series {
+    fn from(series: String) -> Self {
+        Series(series)
+    }
+}
+
+/// A BCP 47 language tag like `en-gb`.
+#[derive(
+    Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash, sqlx::Type, Serialize, Deserialize,
+)]
+#[sqlx(transparent, type_name = "text")]
+pub struct Language(pub String);
+
+impl From<String> for Language {
+    fn from(language: String) -> Self {
+        Language(language)
+    }
+}
+
+/// Where a post is in its lifecycle, see the `post_is_public` and `post_is_accessible` database
+/// functions for what this means for visibility.
+#[derive(
+    Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug, Hash, sqlx::Type, Serialize, Deserialize,
+)]
+#[sqlx(type_name = "post_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PostStatus {
+    /// Only visible through the CLI.
+    Draft,
+    /// Not listed anywhere, meant for sharing with preview tokens.
+    Unlisted,
+    /// Becomes public at the publication date. This behaves exactly like `Published`, which is
+    /// public from its publication date as well, it only records that the date is in the future.
+    Scheduled,
+    Published,
+    /// Public, but no longer maintained.
+    Archived,
+}
+
+/// Which posts [`get_blog_posts`] returns, and in what order.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub enum PostSelection {
+    /// All posts, newest first.
+    #[default]
+    All,
+    /// All posts, but pinned posts come before the others.
+    PinnedFirst,
+    /// Only featured posts, newest first.
+    FeaturedOnly,
+}
+
+/// The position of a post in the order [`get_blog_posts`] returns posts in.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PostKey {
+    /// Only affects the position with [`PostSelection::PinnedFirst`].
+    pub pinned: bool,
+    pub publication_date: Option<DateTime<Utc>>,
+    pub title: String,
+    /// Orders posts with the same publication date and title.
+    pub url: String,
+}
+
+impl PostKey {
+    pub fn of(blog_post: &BlogPost) -> Self {
+        PostKey {
+            pinned: blog_post.pinned,
+            publication_date: blog_post.publication_date,
+            title: blog_post.title.clone(),
+            url: blog_post.url.clone(),
+        }
+    }
+}
+
+impl From<PostKey> for String {
+    fn from(
+        PostKey {
+            pinned,
+            publication_date,
+            title,
+            url,
+        }: PostKey,
+    ) -> Self {
+        let pinned = if pinned { "!" } else { "" };
+        let publication_date = publication_date
+            .as_ref()
+            .map(DateTime::to_rfc3339)
+            .unwrap_or_default();
+        // Escaped so the url never contains the separator, unlike the title which comes last
+        let url = url.replace('%', "%25").replace('~', "%7E");
+
+        format!("{pinned}{publication_date}~{url}~{title}")
+    }
+}
+
+impl TryFrom<String> for PostKey {
+    type Error = chrono::ParseError;
+
+    fn try_from(key: String) -> std::result::Result<Self, Self::Error> {
+        let (publication_date, rest) = key.split_once('~').unwrap_or(("", &key));
+        let (url, title) = rest.split_once('~').unwrap_or(("", rest));
+        let url = url.replace("%7E", "~").replace("%25", "%");
+        let (pinned, publication_date) = match publication_date.strip_prefix('!') {
+            Some(publication_date) => (true, publication_date),
+            None => (false, publication_date),
+        };
+
+        let publication_date = (!publication_date.is_empty())
+            .then(|| DateTime::parse_from_rfc3339(publication_date))
+            .transpose()?
+            .map(|date| date.to_utc());
+
+        Ok(PostKey {
+            pinned,
+            publication_date,
+            title: title.to_string(),
+            url,
+        })
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum PageBoundary {
+    /// The page starts right after this post.
+    After(PostKey),
+    /// The page ends right before this post.
+    Before(PostKey),
+}
+
+impl PageBoundary {
+    pub fn key(&self) -> &PostKey {
+        match self {
+            PageBoundary::After(key) | PageBoundary::Before(key) => key,
+        }
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Page {
+    pub blog_posts: Vec<BlogPost>,
+    /// Boundary for [`PageBoundary::Before`] if there is a previous page.
+    pub previous: Option<PostKey>,
+    /// Boundary for [`PageBoundary::After`] if there is a next page.
+    pub next: Option<PostKey>,
+}
+
 struct BlogPostRecord {
     url: String,
     title: String,
     description: String,
-    author: Author,
+    authors: Vec<String>,
     markdown: Option<String>,
     html: String,
     tags: Option<Vec<String>>,
     reading_time_minutes: i64,
-    accessible: bool,
+    status: PostStatus,
     publication_date: Option<DateTime<Utc>>,
+    updated_at: DateTime<Utc>,
+    series: Option<String>,
+    series_part: Option<i32>,
+    language: String,
+    translation_key: Option<String>,
+    renderer_version: i32,
+    image_urls: Json<HashMap<String, String>>,
+    pinned: bool,
+    featured: bool,
+    outline: Json<Vec<Heading>>,
+    table_of_contents: bool,
+    word_count: i32,
+    excerpt: String,
+    plain_text: String,
 }
 
 impl TryFrom<BlogPostRecord> for BlogPost {
@@ -64,30 +250,222 @@ impl TryFrom<BlogPostRecord> for BlogPost {
             url,
             title,
             description,
-            author,
+            authors,
             markdown,
             html,
             tags,
             reading_time_minutes,
-            accessible,
+            status,
             publication_date,
+            updated_at,
+            series,
+            series_part,
+            language,
+            translation_key,
+            renderer_version,
+            image_urls,
+            pinned,
+            featured,
+            outline,
+            table_of_contents,
+            word_count,
+            excerpt,
+            plain_text,
         }: BlogPostRecord,
     ) -> Result<Self> {
+        let authors = authors.into_iter().map(Author).collect();
         let tags = tags.unwrap_or_default().into_iter().map(Tag).collect();
         let reading_time =
             Duration::try_minutes(reading_time_minutes).ok_or(Error::UnexpectedData)?;
+        let series = match (series, series_part) {
+            (Some(series), Some(part)) => Some(SeriesPart {
+                series: Series(series),
+                part: u32::try_from(part).map_err(|_| Error::UnexpectedData)?,
+            }),
+            (None, None) => None,
+            _ => return Err(Error::UnexpectedData),
+        };
+        let renderer_version =
+            u32::try_from(renderer_version).map_err(|_| Error::UnexpectedData)?;
+        let word_count = u32::try_from(word_count).map_err(|_| Error::UnexpectedData)?;
+        // Stored as an empty string, the column predates optional descriptions
+        let description = Some(description).filter(|description| !description.is_empty());
 
         Ok(BlogPost {
             url,
             title,
             description,
-            author,
+            authors,
             markdown,
             html,
             tags,
             reading_time,
-            accessible,
+            word_count,
+            excerpt,
+            plain_text,
+            status,
+            publication_date,
+            updated_at,
+            series,
+            language: Language(language),
+            translation_key,
+            renderer_version,
+            image_urls: image_urls.0,
+            pinned,
+            featured,
+            outline: outline.0,
+            table_of_contents,
+        })
+    }
+}
+
+struct AuthorRecord {
+    author: Author,
+    display_name: Option<String>,
+    bio_markdown: Option<String>,
+    bio_html: Option<String>,
+    avatar_path: Option<String>,
+    link_labels: Option<Vec<String>>,
+    link_urls: Option<Vec<String>>,
+}
+
+impl TryFrom<AuthorRecord> for AuthorProfile {
+    type Error = Error;
+
+    fn try_from(
+        AuthorRecord {
+            author,
+            display_name,
+            bio_markdown,
+            bio_html,
+            avatar_path,
+            link_labels,
+            link_urls,
+        }: AuthorRecord,
+    ) -> Result<Self> {
+        let link_labels = link_labels.unwrap_or_default();
+        let link_urls = link_urls.unwrap_or_default();
+
+        if link_labels.len() != link_urls.len() {
+            return Err(Error::UnexpectedData);
+        }
+
+        let links = link_labels
+            .into_iter()
+            .zip(link_urls)
+            .map(|(label, url)| AuthorLink { label, url })
+            .collect();
+
+        Ok(AuthorProfile {
+            author,
+            display_name,
+            bio_markdown,
+            bio_html,
+            avatar_path,
+            links,
+        })
+    }
+}
+
+struct BlogPostRevisionRecord {
+    revision: i64,
+    post_url: Option<String>,
+    url: String,
+    title: String,
+    description: String,
+    authors: Vec<String>,
+    markdown: Option<String>,
+    html: String,
+    tags: Vec<String>,
+    reading_time_minutes: i64,
+    status: PostStatus,
+    publication_date: Option<DateTime<Utc>>,
+    updated_at: DateTime<Utc>,
+    series: Option<String>,
+    series_part: Option<i32>,
+    language: String,
+    translation_key: Option<String>,
+    renderer_version: i32,
+    image_urls: Json<HashMap<String, String>>,
+    pinned: bool,
+    featured: bool,
+    outline: Json<Vec<Heading>>,
+    table_of_contents: bool,
+    word_count: i32,
+    excerpt: String,
+    plain_text: String,
+    changed_at: DateTime<Utc>,
+    changed_by: Author,
+}
+
+impl TryFrom<BlogPostRevisionRecord> for BlogPostRevision {
+    type Error = Error;
+
+    fn try_from(
+        BlogPostRevisionRecord {
+            revision,
+            post_url,
+            url,
+            title,
+            description,
+            authors,
+            markdown,
+            html,
+            tags,
+            reading_time_minutes,
+            status,
+            publication_date,
+            updated_at,
+            series,
+            series_part,
+            language,
+            translation_key,
+            renderer_version,
+            image_urls,
+            pinned,
+            featured,
+            outline,
+            table_of_contents,
+            word_count,
+            excerpt,
+            plain_text,
+            changed_at,
+            changed_by,
+        }: BlogPostRevisionRecord,
+    ) -> Result<Self> {
+        let post = BlogPost::try_from(BlogPostRecord {
+            url,
+            title,
+            description,
+            authors,
+            markdown,
+            html,
+            tags: Some(tags),
+            reading_time_minutes,
+            status,
             publication_date,
+            updated_at,
+            series,
+            series_part,
+            language,
+            translation_key,
+            renderer_version,
+            image_urls,
+            pinned,
+            featured,
+            outline,
+            table_of_contents,
+            word_count,
+            excerpt,
+            plain_text,
+        })?;
+
+        Ok(BlogPostRevision {
+            revision,
+            post_url,
+            post,
+            changed_at,
+            changed_by,
         })
     }
 }
@@ -104,6 +482,272 @@ where
         .map_err(Error::from)
 }
 
+/// Storage for the blog, implemented for Postgres, for SQLite with the `sqlite` feature, and by
+/// [`MemoryStore`] for tests and dry runs.
+///
+/// Backends return [`Error::Unsupported`] for operations they don't implement. Things only the CLI
+/// needs, like revisions or author management, stay free functions of the backends that have them.
+#[async_trait]
+pub trait BlogStore: Send + Sync {
+    async fn migrate(&self) -> Result<()>;
+
+    async fn get_blog_post(&self, url: &str, accessible_only: bool) -> Result<Option<BlogPost>>;
+
+    async fn get_blog_posts(
+        &self,
+        authors: Option<&[Author]>,
+        tags: Option<&[Tag]>,
+        languages: Option<&[Language]>,
+        published_only: bool,
+        selection: PostSelection,
+    ) -> Result<Vec<BlogPost>>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn get_blog_posts_page(
+        &self,
+        authors: Option<&[Author]>,
+        tags: Option<&[Tag]>,
+        languages: Option<&[Language]>,
+        published_only: bool,
+        selection: PostSelection,
+        boundary: Option<&PageBoundary>,
+        size: u32,
+    ) -> Result<Page>;
+
+    async fn get_tags(&self, published_only: bool) -> Result<Vec<Tag>>;
+
+    async fn get_languages(&self, published_only: bool) -> Result<Vec<Language>>;
+
+    async fn get_author(&self, author: &Author) -> Result<Option<AuthorProfile>>;
+
+    /// All author profiles, ordered by author.
+    async fn get_authors(&self) -> Result<Vec<AuthorProfile>>;
+
+    async fn search_blog_posts(
+        &self,
+        search_query: &str,
+        published_only: bool,
+    ) -> Result<Vec<SearchResult>>;
+
+    async fn get_series_posts(
+        &self,
+        series: &Series,
+        published_only: bool,
+    ) -> Result<Vec<BlogPost>>;
+
+    async fn get_translations(
+        &self,
+        translation_key: &str,
+        published_only: bool,
+    ) -> Result<Vec<BlogPost>>;
+
+    async fn get_url_redirect(&self, old_url: &str) -> Result<Option<String>>;
+
+    async fn is_valid_preview_token(&self, url: &str, token: &str) -> Result<bool>;
+
+    async fn get_media_asset(&self, hash: &str) -> Result<Option<MediaAsset>>;
+
+    /// The earliest publication date after `after` of a released post, which is when the next
+    /// scheduled post becomes public.
+    async fn get_next_publication_date(
+        &self,
+        after: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>>;
+
+    /// Inserts the post together with the media assets it references in a single transaction.
+    /// `updated_at` is ignored and set to the current time, like on every change.
+    async fn insert_blog_post(
+        &self,
+        blog_post: &BlogPost,
+        media_assets: &[MediaAsset],
+    ) -> Result<()>;
+
+    /// Updates the post together with the media assets it references in a single transaction.
+    /// `changed_by` is only recorded by backends that keep revisions.
+    async fn update_blog_post(
+        &self,
+        original_url: Option<&str>,
+        blog_post: &BlogPost,
+        changed_by: &Author,
+        media_assets: &[MediaAsset],
+    ) -> Result<()>;
+
+    /// Stores the output of a newer renderer for the post at the url of `blog_post`. Only the
+    /// rendered fields are written, and since the contents stay the same, `updated_at` is kept and
+    /// no revision is recorded.
+    async fn update_rendered_blog_post(&self, blog_post: &BlogPost) -> Result<()>;
+}
+
+/// Connects to the backend selected by the scheme of the database url.
+///
+/// `sqlite:` urls need the `sqlite` feature, everything else is treated as Postgres.
+pub async fn connect(database_url: &str) -> Result<Arc<dyn BlogStore>> {
+    if database_url.starts_with("sqlite:") {
+        #[cfg(feature = "sqlite")]
+        return Ok(Arc::new(sqlite::connect(database_url).await?));
+        #[cfg(not(feature = "sqlite"))]
+        return Err(Error::Unsupported);
+    }
+
+    Ok(Arc::new(PgPool::connect(database_url).await?))
+}
+
+/// Channel that [`insert_blog_post`] and the other functions changing a post notify with the url
+/// of the post once their transaction commits.
+pub const BLOG_POST_CHANGED_CHANNEL: &str = "blog_post_changed";
+
+/// Listens on [`BLOG_POST_CHANGED_CHANNEL`], only Postgres supports notifications.
+pub async fn listen_for_blog_post_changes(database_url: &str) -> Result<PgListener> {
+    if database_url.starts_with("sqlite:") {
+        return Err(Error::Unsupported);
+    }
+
+    let mut listener = PgListener::connect(database_url).await?;
+    listener.listen(BLOG_POST_CHANGED_CHANNEL).await?;
+    Ok(listener)
+}
+
+#[async_trait]
+impl BlogStore for PgPool {
+    async fn migrate(&self) -> Result<()> {
+        migrate(self).await
+    }
+
+    async fn get_blog_post(&self, url: &str, accessible_only: bool) -> Result<Option<BlogPost>> {
+        get_blog_post(url, accessible_only, self).await
+    }
+
+    async fn get_blog_posts(
+        &self,
+        authors: Option<&[Author]>,
+        tags: Option<&[Tag]>,
+        languages: Option<&[Language]>,
+        published_only: bool,
+        selection: PostSelection,
+    ) -> Result<Vec<BlogPost>> {
+        get_blog_posts(authors, tags, languages, published_only, selection, self).await
+    }
+
+    async fn get_blog_posts_page(
+        &self,
+        authors: Option<&[Author]>,
+        tags: Option<&[Tag]>,
+        languages: Option<&[Language]>,
+        published_only: bool,
+        selection: PostSelection,
+        boundary: Option<&PageBoundary>,
+        size: u32,
+    ) -> Result<Page> {
+        get_blog_posts_page(
+            authors,
+            tags,
+            languages,
+            published_only,
+            selection,
+            boundary,
+            size,
+            self,
+        )
+        .await
+    }
+
+    async fn get_tags(&self, published_only: bool) -> Result<Vec<Tag>> {
+        get_tags(published_only, self).await
+    }
+
+    async fn get_languages(&self, published_only: bool) -> Result<Vec<Language>> {
+        get_languages(published_only, self).await
+    }
+
+    async fn get_author(&self, author: &Author) -> Result<Option<AuthorProfile>> {
+        get_author(author, self).await
+    }
+
+    async fn get_authors(&self) -> Result<Vec<AuthorProfile>> {
+        get_authors(self).await
+    }
+
+    async fn search_blog_posts(
+        &self,
+        search_query: &str,
+        published_only: bool,
+    ) -> Result<Vec<SearchResult>> {
+        search_blog_posts(search_query, published_only, self).await
+    }
+
+    async fn get_series_posts(
+        &self,
+        series: &Series,
+        published_only: bool,
+    ) -> Result<Vec<BlogPost>> {
+        get_series_posts(series, published_only, self).await
+    }
+
+    async fn get_translations(
+        &self,
+        translation_key: &str,
+        published_only: bool,
+    ) -> Result<Vec<BlogPost>> {
+        get_translations(translation_key, published_only, self).await
+    }
+
+    async fn get_url_redirect(&self, old_url: &str) -> Result<Option<String>> {
+        get_url_redirect(old_url, self).await
+    }
+
+    async fn is_valid_preview_token(&self, url: &str, token: &str) -> Result<bool> {
+        is_valid_preview_token(url, token, self).await
+    }
+
+    async fn get_media_asset(&self, hash: &str) -> Result<Option<MediaAsset>> {
+        get_media_asset(hash, self).await
+    }
+
+    async fn get_next_publication_date(
+        &self,
+        after: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>> {
+        get_next_publication_date(after, self).await
+    }
+
+    async fn insert_blog_post(
+        &self,
+        blog_post: &BlogPost,
+        media_assets: &[MediaAsset],
+    ) -> Result<()> {
+        let mut transaction = self.begin().await?;
+        for media_asset in media_assets {
+            insert_media_asset(media_asset, &mut transaction).await?;
+        }
+        insert_blog_post(blog_post, &mut transaction).await?;
+
+        Ok(transaction.commit().await?)
+    }
+
+    async fn update_blog_post(
+        &self,
+        original_url: Option<&str>,
+        blog_post: &BlogPost,
+        changed_by: &Author,
+        media_assets: &[MediaAsset],
+    ) -> Result<()> {
+        let mut transaction = self.begin().await?;
+        for media_asset in media_assets {
+            insert_media_asset(media_asset, &mut transaction).await?;
+        }
+        update_blog_post(original_url, blog_post, changed_by, &mut transaction).await?;
+
+        Ok(transaction.commit().await?)
+    }
+
+    async fn update_rendered_blog_post(&self, blog_post: &BlogPost) -> Result<()> {
+        let mut transaction = self.begin().await?;
+        update_rendered_blog_post(blog_post, &mut transaction).await?;
+
+        Ok(transaction.commit().await?)
+    }
+}
+
 pub async fn get_blog_post<'c, E: PgExecutor<'c>>(
     url: &str,
     accessible_only: bool,
@@ -113,12 +757,17 @@ pub async fn get_blog_post<'c, E: PgExecutor<'c>>(
 
     query_as!(
         BlogPostRecord,
-        "SELECT url, title, description, author, markdown, html, reading_time_minutes, \
-            accessible, publication_date, array_remove(array_agg(tag ORDER BY tag ASC), NULL) as tags \
+        "SELECT url, title, description, \
+            array(SELECT author FROM post_author WHERE post_author.url = blog_post.url \
+                ORDER BY position ASC) as \"authors!\", \
+            markdown, html, reading_time_minutes, language, translation_key, renderer_version, \
+            image_urls as \"image_urls: Json<HashMap<String, String>>\", pinned, featured, \
+            outline as \"outline: Json<Vec<Heading>>\", table_of_contents, word_count, excerpt, \
+            plain_text, \
+            status as \"status: PostStatus\", publication_date, updated_at, series, series_part, \
+            array_remove(array_agg(tag ORDER BY tag ASC), NULL) as tags \
         FROM blog_post NATURAL LEFT JOIN tag \
-        WHERE url=$1 AND ($2 OR (accessible OR \
-            (publication_date IS NOT NULL \
-            AND publication_date <= now()))) \
+        WHERE url=$1 AND ($2 OR post_is_accessible(status, publication_date)) \
         GROUP BY url",
         url,
         no_accessible_filtering,
@@ -132,36 +781,184 @@ pub async fn get_blog_post<'c, E: PgExecutor<'c>>(
 pub async fn get_blog_posts<'c, E: PgExecutor<'c>>(
     authors: Option<&[Author]>,
     tags: Option<&[Tag]>,
+    languages: Option<&[Language]>,
+    published_only: bool,
+    selection: PostSelection,
+    executor: E,
+) -> Result<Vec<BlogPost>> {
+    query_blog_posts(
+        authors,
+        tags,
+        languages,
+        published_only,
+        selection,
+        None,
+        None,
+        executor,
+    )
+    .await
+}
+
+/// Like [`get_blog_posts`], but returns at most `size` posts starting at `boundary`.
+#[allow(clippy::too_many_arguments)]
+pub async fn get_blog_posts_page<'c, E: PgExecutor<'c>>(
+    authors: Option<&[Author]>,
+    tags: Option<&[Tag]>,
+    languages: Option<&[Language]>,
     published_only: bool,
+    selection: PostSelection,
+    boundary: Option<&PageBoundary>,
+    size: u32,
+    executor: E,
+) -> Result<Page> {
+    // Fetch one more post than requested to know whether there are more pages
+    let blog_posts = query_blog_posts(
+        authors,
+        tags,
+        languages,
+        published_only,
+        selection,
+        boundary,
+        Some(size as i64 + 1),
+        executor,
+    )
+    .await?;
+
+    Ok(page_from_posts(blog_posts, boundary, size))
+}
+
+/// Builds the page from up to `size + 1` posts next to `boundary`, in list order.
+fn page_from_posts(
+    mut blog_posts: Vec<BlogPost>,
+    boundary: Option<&PageBoundary>,
+    size: u32,
+) -> Page {
+    let size = size as usize;
+    let has_more = blog_posts.len() > size;
+
+    let (previous, next) = match boundary {
+        None | Some(PageBoundary::After(_)) => {
+            blog_posts.truncate(size);
+
+            let previous = boundary.map(|boundary| {
+                blog_posts
+                    .first()
+                    .map_or_else(|| boundary.key().clone(), PostKey::of)
+            });
+            let next = blog_posts.last().filter(|_| has_more).map(PostKey::of);
+
+            (previous, next)
+        }
+        Some(PageBoundary::Before(key)) => {
+            if has_more {
+                blog_posts.remove(0);
+            }
+
+            let previous = blog_posts.first().filter(|_| has_more).map(PostKey::of);
+            let next = Some(blog_posts.last().map_or_else(|| key.clone(), PostKey::of));
+
+            (previous, next)
+        }
+    };
+
+    Page {
+        blog_posts,
+        previous,
+        next,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn query_blog_posts<'c, E: PgExecutor<'c>>(
+    authors: Option<&[Author]>,
+    tags: Option<&[Tag]>,
+    languages: Option<&[Language]>,
+    published_only: bool,
+    selection: PostSelection,
+    boundary: Option<&PageBoundary>,
+    limit: Option<i64>,
     executor: E,
 ) -> Result<Vec<BlogPost>> {
     let no_author_filtering = authors.is_none();
     let no_tag_filtering = tags.is_none();
+    let no_language_filtering = languages.is_none();
     let no_public_filtering = !published_only;
+    let featured_only = selection == PostSelection::FeaturedOnly;
+    let pinned_first = selection == PostSelection::PinnedFirst;
+    let backwards = matches!(boundary, Some(PageBoundary::Before(_)));
+    let boundary_key = boundary.map(PageBoundary::key);
+    let boundary_pinned = pinned_first && boundary_key.is_some_and(|key| key.pinned);
 
-    query_as!(
+    // Backwards pages are queried in reverse order, starting at the boundary
+    let mut blog_posts: Vec<_> = query_as!(
         BlogPostRecord,
-        "SELECT url, title, description, author, markdown, html, reading_time_minutes, \
-            accessible, publication_date, array_remove(array_agg(tag ORDER BY tag ASC), NULL) as tags \
+        "SELECT url, title, description, \
+            array(SELECT author FROM post_author WHERE post_author.url = blog_post.url \
+                ORDER BY position ASC) as \"authors!\", \
+            markdown, html, reading_time_minutes, language, translation_key, renderer_version, \
+            image_urls as \"image_urls: Json<HashMap<String, String>>\", pinned, featured, \
+            outline as \"outline: Json<Vec<Heading>>\", table_of_contents, word_count, excerpt, \
+            plain_text, \
+            status as \"status: PostStatus\", publication_date, updated_at, series, series_part, \
+            array_remove(array_agg(tag ORDER BY tag ASC), NULL) as tags \
         FROM blog_post NATURAL LEFT JOIN tag \
         WHERE \
-            ($1 OR author = ANY($2)) \
-            AND ($3 OR (publication_date IS NOT NULL \
-                AND publication_date <= now())) \
+            ($1 OR EXISTS(SELECT * FROM post_author \
+                WHERE post_author.url = blog_post.url AND author = ANY($2))) \
+            AND ($3 OR post_is_public(status, publication_date)) \
+            AND ($10 OR language = ANY($11)) \
+            AND (NOT $14 OR featured) \
+            AND ($7::text IS NULL \
+                OR (NOT $8 AND (($13 AND NOT ($12 AND pinned)) \
+                    OR (($12 AND pinned) = $13 AND (($6::timestamptz IS NULL \
+                        AND publication_date IS NULL AND (title, url) > ($7, $15)) \
+                    OR ($6 IS NOT NULL AND (publication_date IS NULL \
+                        OR publication_date < $6 \
+                        OR (publication_date = $6 AND (title, url) > ($7, $15)))))))) \
+                OR ($8 AND ((NOT $13 AND $12 AND pinned) \
+                    OR (($12 AND pinned) = $13 AND (($6 IS NULL \
+                        AND (publication_date IS NOT NULL OR (title, url) < ($7, $15))) \
+                    OR ($6 IS NOT NULL AND (publication_date > $6 \
+                        OR (publication_date = $6 AND (title, url) < ($7, $15))))))))) \
         GROUP BY url \
         HAVING $4 OR bool_or(tag = ANY($5)) \
-        ORDER BY publication_date DESC NULLS LAST, title ASC",
+        ORDER BY \
+            (CASE WHEN $8 THEN NULL ELSE $12 AND pinned END) DESC, \
+            (CASE WHEN $8 THEN NULL ELSE publication_date END) DESC NULLS LAST, \
+            (CASE WHEN $8 THEN NULL ELSE title END) ASC, \
+            (CASE WHEN $8 THEN NULL ELSE url END) ASC, \
+            (CASE WHEN $8 THEN $12 AND pinned END) ASC, \
+            (CASE WHEN $8 THEN publication_date END) ASC NULLS FIRST, \
+            (CASE WHEN $8 THEN title END) DESC, \
+            (CASE WHEN $8 THEN url END) DESC \
+        LIMIT $9",
         no_author_filtering,
         &authors.unwrap_or_default() as &[Author],
         no_public_filtering,
         no_tag_filtering,
         &tags.unwrap_or_default() as &[Tag],
+        boundary_key.and_then(|key| key.publication_date),
+        boundary_key.map(|key| &key.title),
+        backwards,
+        limit,
+        no_language_filtering,
+        &languages.unwrap_or_default() as &[Language],
+        pinned_first,
+        boundary_pinned,
+        featured_only,
+        boundary_key.map(|key| &key.url),
     )
     .fetch(executor)
     .map_err(Error::from)
     .map(|result| result.and_then(BlogPost::try_from))
     .try_collect()
-    .await
+    .await?;
+
+    if backwards {
+        blog_posts.reverse();
+    }
+
+    Ok(blog_posts)
 }
 
 pub async fn get_tags<'c, E: PgExecutor<'c>>(
@@ -173,8 +970,7 @@ pub async fn get_tags<'c, E: PgExecutor<'c>>(
     query_scalar!(
         "SELECT tag \
         FROM tag NATURAL LEFT JOIN blog_post WHERE \
-            ($1 OR (publication_date IS NOT NULL \
-                AND publication_date <= now())) \
+            ($1 OR post_is_public(status, publication_date)) \
         GROUP BY tag \
         ORDER BY tag ASC",
         no_public_filtering,
@@ -186,48 +982,383 @@ pub async fn get_tags<'c, E: PgExecutor<'c>>(
     .map_err(Error::from)
 }
 
-pub async fn insert_blog_post<'c>(
-    BlogPost {
-        url,
-        title,
-        description,
-        author,
-        markdown,
-        html,
-        tags,
-        reading_time,
-        accessible,
-        publication_date,
-    }: &BlogPost,
-    new_author: bool,
-    transaction: &mut Transaction<'c, Postgres>,
-) -> Result<()> {
-    // Insert author (if new)
-    if new_author {
-        query!(
-            "INSERT INTO author (author) \
-            VALUES ($1)",
-            author.0,
-        )
-        .execute(&mut **transaction)
-        .await?;
-    }
+/// All languages posts are written in.
+pub async fn get_languages<'c, E: PgExecutor<'c>>(
+    published_only: bool,
+    executor: E,
+) -> Result<Vec<Language>> {
+    let no_public_filtering = !published_only;
 
-    // Insert blog post
-    query!(
-        "INSERT INTO blog_post \
-            (url, title, description, author, markdown, html, reading_time_minutes, \
-                accessible, publication_date) \
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
-        url,
-        title,
-        description,
-        author.0,
-        markdown.as_ref(),
-        html,
-        i32::try_from(reading_time.num_minutes()).map_err(|_| Error::InvalidInput)?,
-        accessible,
-        publication_date.as_ref(),
+    query_scalar!(
+        "SELECT DISTINCT language \
+        FROM blog_post WHERE \
+            ($1 OR post_is_public(status, publication_date)) \
+        ORDER BY language ASC",
+        no_public_filtering,
+    )
+    .fetch(executor)
+    .map_ok(Language)
+    .try_collect()
+    .await
+    .map_err(Error::from)
+}
+
+pub async fn get_author<'c, E: PgExecutor<'c>>(
+    author: &Author,
+    executor: E,
+) -> Result<Option<AuthorProfile>> {
+    query_as!(
+        AuthorRecord,
+        "SELECT author, display_name, bio_markdown, bio_html, avatar_path, \
+            array_remove(array_agg(label ORDER BY position ASC), NULL) as link_labels, \
+            array_remove(array_agg(url ORDER BY position ASC), NULL) as link_urls \
+        FROM author NATURAL LEFT JOIN author_link \
+        WHERE author = $1 \
+        GROUP BY author",
+        author.0,
+    )
+    .fetch_optional(executor)
+    .await?
+    .map(AuthorProfile::try_from)
+    .transpose()
+}
+
+pub async fn get_authors<'c, E: PgExecutor<'c>>(executor: E) -> Result<Vec<AuthorProfile>> {
+    query_as!(
+        AuthorRecord,
+        "SELECT author, display_name, bio_markdown, bio_html, avatar_path, \
+            array_remove(array_agg(label ORDER BY position ASC), NULL) as link_labels, \
+            array_remove(array_agg(url ORDER BY position ASC), NULL) as link_urls \
+        FROM author NATURAL LEFT JOIN author_link \
+        GROUP BY author \
+        ORDER BY author ASC",
+    )
+    .fetch(executor)
+    .map_err(Error::from)
+    .map(|result| result.and_then(AuthorProfile::try_from))
+    .try_collect()
+    .await
+}
+
+pub async fn insert_author<'c>(
+    author_profile: &AuthorProfile,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    query!(
+        "INSERT INTO author (author) \
+        VALUES ($1)",
+        author_profile.author.0,
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    update_author(author_profile, transaction).await
+}
+
+pub async fn update_author<'c>(
+    AuthorProfile {
+        author,
+        display_name,
+        bio_markdown,
+        bio_html,
+        avatar_path,
+        links,
+    }: &AuthorProfile,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    let updated = query!(
+        "UPDATE author \
+        SET display_name=$1, bio_markdown=$2, bio_html=$3, avatar_path=$4 \
+        WHERE author = $5",
+        display_name.as_ref(),
+        bio_markdown.as_ref(),
+        bio_html.as_ref(),
+        avatar_path.as_ref(),
+        author.0,
+    )
+    .execute(&mut **transaction)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    // Replace links
+    query!(
+        "DELETE FROM author_link \
+        WHERE author = $1",
+        author.0,
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    for (position, AuthorLink { label, url }) in links.iter().enumerate() {
+        query!(
+            "INSERT INTO author_link (author, position, label, url) \
+            VALUES ($1, $2, $3, $4)",
+            author.0,
+            i32::try_from(position).map_err(|_| Error::InvalidInput)?,
+            label,
+            url,
+        )
+        .execute(&mut **transaction)
+        .await?;
+    }
+
+    // Post pages show the display name of their authors
+    notify_blog_post_changed(&author.0, transaction).await
+}
+
+/// Delimiters `ts_headline` puts around matches. These are control characters so they can't
+/// clash with post contents, which lets us escape the snippet when rendering.
+const HEADLINE_START: char = '\u{2}';
+const HEADLINE_STOP: char = '\u{3}';
+
+fn snippet_fragments(headline: &str) -> Vec<SnippetFragment> {
+    let mut fragments = Vec::new();
+
+    for (idx, unhighlighted) in headline.split(HEADLINE_START).enumerate() {
+        let (highlighted, rest) = match unhighlighted.split_once(HEADLINE_STOP) {
+            // Everything before the first start delimiter is not highlighted
+            Some(split) if idx > 0 => split,
+            _ => ("", unhighlighted),
+        };
+
+        for (text, highlighted) in [(highlighted, true), (rest, false)] {
+            if !text.is_empty() {
+                fragments.push(SnippetFragment {
+                    text: text.to_string(),
+                    highlighted,
+                });
+            }
+        }
+    }
+
+    fragments
+}
+
+/// Full-text search over title, description and plain text, ordered by relevance. English posts
+/// are matched by word stems, others word by word, see the `search_config` database function.
+pub async fn search_blog_posts<'c, E: PgExecutor<'c>>(
+    search_query: &str,
+    published_only: bool,
+    executor: E,
+) -> Result<Vec<SearchResult>> {
+    let no_public_filtering = !published_only;
+    let headline_options = format!(
+        "StartSel={HEADLINE_START}, StopSel={HEADLINE_STOP}, \
+        MaxFragments=2, MinWords=10, MaxWords=30"
+    );
+
+    query!(
+        "SELECT url, title, description, \
+            array(SELECT author FROM post_author WHERE post_author.url = blog_post.url \
+                ORDER BY position ASC) as \"authors!\", \
+            markdown, html, reading_time_minutes, language, translation_key, renderer_version, \
+            image_urls as \"image_urls: Json<HashMap<String, String>>\", pinned, featured, \
+            outline as \"outline: Json<Vec<Heading>>\", table_of_contents, word_count, excerpt, \
+            plain_text, \
+            status as \"status: PostStatus\", publication_date, updated_at, series, series_part, \
+            array_remove(array_agg(tag ORDER BY tag ASC), NULL) as tags, \
+            ts_headline(search_config(language), coalesce(nullif(plain_text, ''), description), \
+                search_query, $3) as \"headline!\" \
+        FROM blog_post NATURAL LEFT JOIN tag \
+            CROSS JOIN LATERAL websearch_to_tsquery(search_config(language), $1) search_query \
+        WHERE search_vector @@ search_query \
+            AND ($2 OR post_is_public(status, publication_date)) \
+        GROUP BY url, search_query \
+        ORDER BY ts_rank(search_vector, search_query) DESC, \
+            publication_date DESC NULLS LAST, title ASC, url ASC",
+        search_query,
+        no_public_filtering,
+        headline_options,
+    )
+    .fetch(executor)
+    .map_err(Error::from)
+    .map(|result| {
+        let record = result?;
+
+        let blog_post = BlogPost::try_from(BlogPostRecord {
+            url: record.url,
+            title: record.title,
+            description: record.description,
+            authors: record.authors,
+            markdown: record.markdown,
+            html: record.html,
+            tags: record.tags,
+            reading_time_minutes: record.reading_time_minutes.into(),
+            status: record.status,
+            publication_date: record.publication_date,
+            updated_at: record.updated_at,
+            series: record.series,
+            series_part: record.series_part,
+            language: record.language,
+            translation_key: record.translation_key,
+            renderer_version: record.renderer_version,
+            image_urls: record.image_urls,
+            pinned: record.pinned,
+            featured: record.featured,
+            outline: record.outline,
+            table_of_contents: record.table_of_contents,
+            word_count: record.word_count,
+            excerpt: record.excerpt,
+            plain_text: record.plain_text,
+        })?;
+
+        Ok(SearchResult {
+            blog_post,
+            snippet: snippet_fragments(&record.headline),
+        })
+    })
+    .try_collect()
+    .await
+}
+
+/// All posts of a series, ordered by their part number.
+pub async fn get_series_posts<'c, E: PgExecutor<'c>>(
+    series: &Series,
+    published_only: bool,
+    executor: E,
+) -> Result<Vec<BlogPost>> {
+    let no_public_filtering = !published_only;
+
+    query_as!(
+        BlogPostRecord,
+        "SELECT url, title, description, \
+            array(SELECT author FROM post_author WHERE post_author.url = blog_post.url \
+                ORDER BY position ASC) as \"authors!\", \
+            markdown, html, reading_time_minutes, language, translation_key, renderer_version, \
+            image_urls as \"image_urls: Json<HashMap<String, String>>\", pinned, featured, \
+            outline as \"outline: Json<Vec<Heading>>\", table_of_contents, word_count, excerpt, \
+            plain_text, \
+            status as \"status: PostStatus\", publication_date, updated_at, series, series_part, \
+            array_remove(array_agg(tag ORDER BY tag ASC), NULL) as tags \
+        FROM blog_post NATURAL LEFT JOIN tag \
+        WHERE series = $1 \
+            AND ($2 OR post_is_public(status, publication_date)) \
+        GROUP BY url \
+        ORDER BY series_part ASC",
+        series.0,
+        no_public_filtering,
+    )
+    .fetch(executor)
+    .map_err(Error::from)
+    .map(|result| result.and_then(BlogPost::try_from))
+    .try_collect()
+    .await
+}
+
+/// All translations of an article, including the post itself, ordered by language.
+pub async fn get_translations<'c, E: PgExecutor<'c>>(
+    translation_key: &str,
+    published_only: bool,
+    executor: E,
+) -> Result<Vec<BlogPost>> {
+    let no_public_filtering = !published_only;
+
+    query_as!(
+        BlogPostRecord,
+        "SELECT url, title, description, \
+            array(SELECT author FROM post_author WHERE post_author.url = blog_post.url \
+                ORDER BY position ASC) as \"authors!\", \
+            markdown, html, reading_time_minutes, language, translation_key, renderer_version, \
+            image_urls as \"image_urls: Json<HashMap<String, String>>\", pinned, featured, \
+            outline as \"outline: Json<Vec<Heading>>\", table_of_contents, word_count, excerpt, \
+            plain_text, \
+            status as \"status: PostStatus\", publication_date, updated_at, series, series_part, \
+            array_remove(array_agg(tag ORDER BY tag ASC), NULL) as tags \
+        FROM blog_post NATURAL LEFT JOIN tag \
+        WHERE translation_key = $1 \
+            AND ($2 OR post_is_public(status, publication_date)) \
+        GROUP BY url \
+        ORDER BY language ASC",
+        translation_key,
+        no_public_filtering,
+    )
+    .fetch(executor)
+    .map_err(Error::from)
+    .map(|result| result.and_then(BlogPost::try_from))
+    .try_collect()
+    .await
+}
+
+fn series_part(series: Option<&SeriesPart>) -> Result<Option<i32>> {
+    series
+        .map(|series| i32::try_from(series.part).map_err(|_| Error::InvalidInput))
+        .transpose()
+}
+
+pub async fn insert_blog_post<'c>(
+    BlogPost {
+        url,
+        title,
+        description,
+        authors,
+        markdown,
+        html,
+        tags,
+        reading_time,
+        word_count,
+        excerpt,
+        plain_text,
+        status,
+        publication_date,
+        series,
+        language,
+        translation_key,
+        renderer_version,
+        image_urls,
+        pinned,
+        featured,
+        outline,
+        table_of_contents,
+        // Set to the time of the change by the database
+        updated_at: _,
+    }: &BlogPost,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    let main_author = authors.first().ok_or(Error::InvalidInput)?;
+
+    // A live post takes precedence over redirects
+    query!(
+        "DELETE FROM url_redirect \
+        WHERE old_url = $1",
+        url,
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    // Insert blog post
+    query!(
+        "INSERT INTO blog_post \
+            (url, title, description, markdown, html, reading_time_minutes, status, \
+                publication_date, updated_at, series, series_part, language, translation_key, \
+                renderer_version, image_urls, pinned, featured, outline, table_of_contents, \
+                word_count, excerpt, plain_text) \
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, now(), $9, $10, $11, $12, $13, $14, $15, $16, \
+            $17, $18, $19, $20, $21)",
+        url,
+        title,
+        description.as_deref().unwrap_or_default(),
+        markdown.as_ref(),
+        html,
+        i32::try_from(reading_time.num_minutes()).map_err(|_| Error::InvalidInput)?,
+        *status as PostStatus,
+        publication_date.as_ref(),
+        series.as_ref().map(|series| &series.series.0),
+        series_part(series.as_ref())?,
+        language.0,
+        translation_key.as_ref(),
+        i32::try_from(*renderer_version).map_err(|_| Error::InvalidInput)?,
+        Json(image_urls) as _,
+        pinned,
+        featured,
+        Json(outline) as _,
+        table_of_contents,
+        i32::try_from(*word_count).map_err(|_| Error::InvalidInput)?,
+        excerpt,
+        plain_text,
     )
     .execute(&mut **transaction)
     .await?;
@@ -244,7 +1375,10 @@ pub async fn insert_blog_post<'c>(
         .await?;
     }
 
-    Ok(())
+    replace_post_authors(url, authors, transaction).await?;
+
+    insert_blog_post_revision(url, main_author, transaction).await?;
+    notify_blog_post_changed(url, transaction).await
 }
 
 pub async fn update_blog_post<'c>(
@@ -253,65 +1387,113 @@ pub async fn update_blog_post<'c>(
         url,
         title,
         description,
-        author,
+        authors,
         markdown,
         html,
         tags,
         reading_time,
-        accessible,
+        word_count,
+        excerpt,
+        plain_text,
+        status,
         publication_date,
+        series,
+        language,
+        translation_key,
+        renderer_version,
+        image_urls,
+        pinned,
+        featured,
+        outline,
+        table_of_contents,
+        // Set to the time of the change by the database
+        updated_at: _,
     }: &BlogPost,
-    new_author: bool,
+    changed_by: &Author,
     transaction: &mut Transaction<'c, Postgres>,
 ) -> Result<()> {
     let original_url = original_url.unwrap_or(url);
 
-    // Insert author (if new)
-    if new_author {
-        query!(
-            "INSERT INTO author (author) \
-            VALUES ($1)",
-            author.0,
-        )
-        .execute(&mut **transaction)
-        .await?;
+    if authors.is_empty() {
+        return Err(Error::InvalidInput);
     }
 
-    // Remove old tags
+    // Remove tags that are no longer in the tag list
     query!(
         "DELETE FROM tag \
-        WHERE tag = ANY($1) AND url = $2",
-        &tags.iter().map(|Tag(tag)| tag.clone()).collect::<Vec<_>>(),
+        WHERE url = $1 AND NOT (tag = ANY($2))",
         original_url,
+        &tags.iter().map(|Tag(tag)| tag.clone()).collect::<Vec<_>>(),
     )
     .execute(&mut **transaction)
     .await?;
 
     // Update blog post
-    query!(
+    let updated = query!(
         "UPDATE blog_post \
-        SET url=$1, title=$2, description=$3, author=$4, markdown=$5, html=$6, \
-            reading_time_minutes=$7, accessible=$8, publication_date=$9 \
-        WHERE url = $10",
+        SET url=$1, title=$2, description=$3, markdown=$4, html=$5, reading_time_minutes=$6, \
+            status=$7, publication_date=$8, updated_at=now(), series=$9, series_part=$10, \
+            language=$11, translation_key=$12, renderer_version=$13, image_urls=$14, pinned=$15, \
+            featured=$16, outline=$17, table_of_contents=$18, word_count=$19, excerpt=$20, \
+            plain_text=$21 \
+        WHERE url = $22",
         url,
         title,
-        description,
-        author.0,
+        description.as_deref().unwrap_or_default(),
         markdown.as_ref(),
         html,
         i32::try_from(reading_time.num_minutes()).map_err(|_| Error::InvalidInput)?,
-        accessible,
+        *status as PostStatus,
         publication_date.as_ref(),
+        series.as_ref().map(|series| &series.series.0),
+        series_part(series.as_ref())?,
+        language.0,
+        translation_key.as_ref(),
+        i32::try_from(*renderer_version).map_err(|_| Error::InvalidInput)?,
+        Json(image_urls) as _,
+        pinned,
+        featured,
+        Json(outline) as _,
+        table_of_contents,
+        i32::try_from(*word_count).map_err(|_| Error::InvalidInput)?,
+        excerpt,
+        plain_text,
         original_url,
     )
     .execute(&mut **transaction)
-    .await?;
+    .await?
+    .rows_affected();
 
-    // Insert tags
+    if updated == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    // Redirect the old url to the new one
+    if original_url != url {
+        query!(
+            "DELETE FROM url_redirect \
+            WHERE old_url = $1",
+            url,
+        )
+        .execute(&mut **transaction)
+        .await?;
+
+        query!(
+            "INSERT INTO url_redirect (old_url, new_url) \
+            VALUES ($1, $2)",
+            original_url,
+            url,
+        )
+        .execute(&mut **transaction)
+        .await?;
+    }
+
+    // Insert new tags, the remaining ones have followed the url change
     for Tag(tag) in tags {
         query!(
             "INSERT INTO tag (tag, url) \
-            VALUES ($1, $2)",
+            VALUES ($1, $2) \
+            ON CONFLICT DO NOTHING",
             tag,
             url,
         )
@@ -319,16 +1501,446 @@ pub async fn update_blog_post<'c>(
         .await?;
     }
 
-    Ok(())
+    replace_post_authors(url, authors, transaction).await?;
+
+    insert_blog_post_revision(url, changed_by, transaction).await?;
+    notify_blog_post_changed(url, transaction).await
+}
+
+/// Writes the fields the renderer produces, see [`BlogStore::update_rendered_blog_post`].
+pub async fn update_rendered_blog_post<'c>(
+    BlogPost {
+        url,
+        html,
+        reading_time,
+        word_count,
+        excerpt,
+        plain_text,
+        renderer_version,
+        outline,
+        ..
+    }: &BlogPost,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    let updated = query!(
+        "UPDATE blog_post \
+        SET html=$1, reading_time_minutes=$2, renderer_version=$3, outline=$4, word_count=$5, \
+            excerpt=$6, plain_text=$7 \
+        WHERE url = $8",
+        html,
+        i32::try_from(reading_time.num_minutes()).map_err(|_| Error::InvalidInput)?,
+        i32::try_from(*renderer_version).map_err(|_| Error::InvalidInput)?,
+        Json(outline) as _,
+        i32::try_from(*word_count).map_err(|_| Error::InvalidInput)?,
+        excerpt,
+        plain_text,
+        url,
+    )
+    .execute(&mut **transaction)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    notify_blog_post_changed(url, transaction).await
+}
+
+async fn replace_post_authors<'c>(
+    url: &str,
+    authors: &[Author],
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    query!(
+        "DELETE FROM post_author \
+        WHERE url = $1",
+        url,
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    for (position, author) in authors.iter().enumerate() {
+        query!(
+            "INSERT INTO post_author (url, author, position) \
+            VALUES ($1, $2, $3)",
+            url,
+            author.0,
+            i32::try_from(position).map_err(|_| Error::InvalidInput)?,
+        )
+        .execute(&mut **transaction)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Snapshots the current state of the post at `url` into `blog_post_revision`.
+async fn insert_blog_post_revision<'c>(
+    url: &str,
+    changed_by: &Author,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    query!(
+        "INSERT INTO blog_post_revision \
+            (post_url, url, title, description, authors, markdown, html, tags, \
+                reading_time_minutes, status, publication_date, updated_at, series, series_part, \
+                language, translation_key, renderer_version, image_urls, pinned, featured, \
+                outline, table_of_contents, word_count, excerpt, plain_text, changed_by) \
+        SELECT url, url, title, description, \
+            array(SELECT author FROM post_author WHERE post_author.url = blog_post.url \
+                ORDER BY position ASC), \
+            markdown, html, \
+            array(SELECT tag FROM tag WHERE tag.url = blog_post.url ORDER BY tag ASC), \
+            reading_time_minutes, status, publication_date, updated_at, series, series_part, \
+            language, translation_key, renderer_version, image_urls, pinned, featured, \
+            outline, table_of_contents, word_count, excerpt, plain_text, $2 \
+        FROM blog_post \
+        WHERE url = $1",
+        url,
+        changed_by.0,
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    Ok(())
+}
+
+/// Notifies [`BLOG_POST_CHANGED_CHANNEL`] listeners after the transaction commits. `url` is only
+/// informational, listeners clear everything.
+async fn notify_blog_post_changed<'c>(
+    url: &str,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    query!("SELECT pg_notify($1, $2)", BLOG_POST_CHANGED_CHANNEL, url,)
+        .execute(&mut **transaction)
+        .await?;
+
+    Ok(())
+}
+
+/// Revisions of the post at `url`, and of deleted posts that were last saved at `url`.
+pub async fn get_blog_post_revisions<'c, E: PgExecutor<'c>>(
+    url: &str,
+    executor: E,
+) -> Result<Vec<BlogPostRevision>> {
+    query_as!(
+        BlogPostRevisionRecord,
+        "SELECT revision, post_url, url, title, description, authors, markdown, html, tags, \
+            reading_time_minutes, status as \"status: PostStatus\", publication_date, updated_at, \
+            series, series_part, language, translation_key, renderer_version, \
+            image_urls as \"image_urls: Json<HashMap<String, String>>\", pinned, featured, \
+            outline as \"outline: Json<Vec<Heading>>\", table_of_contents, word_count, excerpt, \
+            plain_text, \
+            changed_at, changed_by \
+        FROM blog_post_revision \
+        WHERE post_url = $1 OR (post_url IS NULL AND url = $1) \
+        ORDER BY revision ASC",
+        url,
+    )
+    .fetch(executor)
+    .map_err(Error::from)
+    .map(|result| result.and_then(BlogPostRevision::try_from))
+    .try_collect()
+    .await
+}
+
+pub async fn get_blog_post_revision<'c, E: PgExecutor<'c>>(
+    revision: i64,
+    executor: E,
+) -> Result<Option<BlogPostRevision>> {
+    query_as!(
+        BlogPostRevisionRecord,
+        "SELECT revision, post_url, url, title, description, authors, markdown, html, tags, \
+            reading_time_minutes, status as \"status: PostStatus\", publication_date, updated_at, \
+            series, series_part, language, translation_key, renderer_version, \
+            image_urls as \"image_urls: Json<HashMap<String, String>>\", pinned, featured, \
+            outline as \"outline: Json<Vec<Heading>>\", table_of_contents, word_count, excerpt, \
+            plain_text, \
+            changed_at, changed_by \
+        FROM blog_post_revision \
+        WHERE revision = $1",
+        revision,
+    )
+    .fetch_optional(executor)
+    .await?
+    .map(BlogPostRevision::try_from)
+    .transpose()
+}
+
+/// Returns the current url of the post that used to be at `old_url`.
+pub async fn get_url_redirect<'c, E: PgExecutor<'c>>(
+    old_url: &str,
+    executor: E,
+) -> Result<Option<String>> {
+    query_scalar!(
+        "SELECT new_url \
+        FROM url_redirect \
+        WHERE old_url = $1",
+        old_url,
+    )
+    .fetch_optional(executor)
+    .await
+    .map_err(Error::from)
+}
+
+/// Creates a new random preview token for the post at `url`.
+pub async fn create_preview_token<'c>(
+    url: &str,
+    created_by: &Author,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<PreviewToken> {
+    // gen_random_uuid uses a cryptographically secure random number generator
+    query_as!(
+        PreviewToken,
+        "INSERT INTO preview_token (token, post_url, created_by) \
+        VALUES (replace(gen_random_uuid()::text, '-', ''), $1, $2) \
+        RETURNING token, post_url, created_at, created_by",
+        url,
+        created_by.0,
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(Error::from)
+}
+
+pub async fn get_preview_tokens<'c, E: PgExecutor<'c>>(
+    url: &str,
+    executor: E,
+) -> Result<Vec<PreviewToken>> {
+    query_as!(
+        PreviewToken,
+        "SELECT token, post_url, created_at, created_by \
+        FROM preview_token \
+        WHERE post_url = $1 \
+        ORDER BY created_at ASC, token ASC",
+        url,
+    )
+    .fetch_all(executor)
+    .await
+    .map_err(Error::from)
+}
+
+/// Whether `token` gives access to the post at `url`.
+pub async fn is_valid_preview_token<'c, E: PgExecutor<'c>>(
+    url: &str,
+    token: &str,
+    executor: E,
+) -> Result<bool> {
+    query_scalar!(
+        "SELECT EXISTS(SELECT * FROM preview_token WHERE post_url = $1 AND token = $2) \
+            as \"exists!\"",
+        url,
+        token,
+    )
+    .fetch_one(executor)
+    .await
+    .map_err(Error::from)
+}
+
+pub async fn revoke_preview_token<'c>(
+    token: &str,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    let deleted = query!(
+        "DELETE FROM preview_token \
+        WHERE token = $1",
+        token,
+    )
+    .execute(&mut **transaction)
+    .await?
+    .rows_affected();
+
+    if deleted == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    Ok(())
+}
+
+/// Stores `media_asset` unless an asset with the same hash already exists.
+pub async fn insert_media_asset<'c>(
+    MediaAsset {
+        hash,
+        content_type,
+        data,
+    }: &MediaAsset,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    query!(
+        "INSERT INTO media_asset (hash, content_type, data) \
+        VALUES ($1, $2, $3) \
+        ON CONFLICT DO NOTHING",
+        hash,
+        content_type,
+        data,
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_media_asset<'c, E: PgExecutor<'c>>(
+    hash: &str,
+    executor: E,
+) -> Result<Option<MediaAsset>> {
+    query_as!(
+        MediaAsset,
+        "SELECT hash, content_type, data \
+        FROM media_asset \
+        WHERE hash = $1",
+        hash,
+    )
+    .fetch_optional(executor)
+    .await
+    .map_err(Error::from)
+}
+
+/// The earliest publication date after `after` of a released post, which is when the next
+/// scheduled post becomes public.
+pub async fn get_next_publication_date<'c, E: PgExecutor<'c>>(
+    after: DateTime<Utc>,
+    executor: E,
+) -> Result<Option<DateTime<Utc>>> {
+    query_scalar!(
+        "SELECT min(publication_date) \
+        FROM blog_post \
+        WHERE status IN ('scheduled', 'published', 'archived') AND publication_date > $1",
+        after,
+    )
+    .fetch_one(executor)
+    .await
+    .map_err(Error::from)
+}
+
+/// Restores every field of the post at `url` to the state stored in `revision`, except for
+/// `updated_at` which is set to the current time like for every change. The rollback itself is
+/// recorded as a new revision.
+pub async fn rollback_blog_post<'c>(
+    url: &str,
+    revision: i64,
+    changed_by: &Author,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    let BlogPostRevision { post_url, post, .. } =
+        get_blog_post_revision(revision, &mut **transaction)
+            .await?
+            .ok_or(Error::InvalidInput)?;
+
+    if post_url.as_deref() != Some(url) {
+        return Err(Error::InvalidInput);
+    }
+
+    update_blog_post(Some(url), &post, changed_by, transaction).await
+}
+
+/// Deletes the post at `url` together with its tags. Its revisions are kept, see
+/// [`BlogPostRevision::post_url`].
+pub async fn delete_blog_post<'c>(
+    url: &str,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    // Remove tags
+    query!(
+        "DELETE FROM tag \
+        WHERE url = $1",
+        url,
+    )
+    .execute(&mut **transaction)
+    .await?;
+
+    // Delete blog post
+    let deleted = query!(
+        "DELETE FROM blog_post \
+        WHERE url = $1",
+        url,
+    )
+    .execute(&mut **transaction)
+    .await?
+    .rows_affected();
+
+    if deleted == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    notify_blog_post_changed(url, transaction).await
+}
+
+/// Archived posts stay listed, but are marked as no longer maintained.
+/// Only published posts can be archived, unarchiving makes them published again. The contents
+/// stay the same, so `updated_at` is kept.
+pub async fn archive_blog_post<'c>(
+    url: &str,
+    archived: bool,
+    changed_by: &Author,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    let status = if archived {
+        PostStatus::Archived
+    } else {
+        PostStatus::Published
+    };
+
+    let updated = query!(
+        "UPDATE blog_post \
+        SET status=$1 \
+        WHERE url = $2 AND status IN ('published', 'archived')",
+        status as PostStatus,
+        url,
+    )
+    .execute(&mut **transaction)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    insert_blog_post_revision(url, changed_by, transaction).await?;
+    notify_blog_post_changed(url, transaction).await
+}
+
+/// Removes the publication date and turns the post back into a draft, keeping `updated_at`.
+pub async fn unpublish_blog_post<'c>(
+    url: &str,
+    changed_by: &Author,
+    transaction: &mut Transaction<'c, Postgres>,
+) -> Result<()> {
+    let updated = query!(
+        "UPDATE blog_post \
+        SET status='draft', publication_date=NULL \
+        WHERE url = $1",
+        url,
+    )
+    .execute(&mut **transaction)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    insert_blog_post_revision(url, changed_by, transaction).await?;
+    notify_blog_post_changed(url, transaction).await
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::blog::BlogPost;
-    use crate::database::{Author, Tag};
+    use crate::blog::{
+        ignoring_update_times, AuthorLink, AuthorProfile, BlogPost, BlogPostRevision, SearchResult,
+        SeriesPart, SnippetFragment,
+    };
+    use crate::database::{
+        Author, BlogStore, Language, PageBoundary, PostKey, PostSelection, PostStatus, Series, Tag,
+    };
+    use crate::markdown_render::{Heading, RENDERER_VERSION};
+    use crate::media::MediaAsset;
     use chrono::{DateTime, Duration};
     use itertools::Itertools;
+    use sqlx::postgres::PgListener;
     use sqlx::PgPool;
+    use std::collections::HashMap;
 
     struct ExpectedBlogPosts {
         public: BlogPost,
@@ -347,68 +1959,133 @@ mod tests {
                 public: BlogPost {
                     url: "public".to_string(),
                     title: "Test (Public)".to_string(),
-                    description: "No description".to_string(),
-                    author: Author("Quiet".to_string()),
+                    description: Some("No description".to_string()),
+                    authors: vec![Author("Quiet".to_string())],
                     markdown: Some("test *bold*".to_string()),
                     html: "test <b>bold</b>".to_string(),
                     tags: vec![Tag("post".to_string()), Tag("public".to_string())],
                     reading_time: Duration::minutes(1),
-                    accessible: false,
+                    word_count: 0,
+                    excerpt: String::new(),
+                    plain_text: "test bold".to_string(),
+                    status: PostStatus::Published,
                     publication_date: Some(DateTime::UNIX_EPOCH),
+                    updated_at: DateTime::from_timestamp(86_400, 0).unwrap(),
+                    series: None,
+                    language: Language("en-gb".to_string()),
+                    translation_key: Some("test".to_string()),
+                    renderer_version: 0,
+                    image_urls: HashMap::new(),
+                    pinned: false,
+                    featured: false,
+                    outline: Vec::new(),
+                    table_of_contents: false,
                 },
                 accessible: BlogPost {
                     url: "accessible".to_string(),
                     title: "Test (Accessible)".to_string(),
-                    description: "No description".to_string(),
-                    author: Author("Quiet".to_string()),
+                    description: Some("No description".to_string()),
+                    authors: vec![Author("Quiet".to_string())],
                     markdown: Some("test2".to_string()),
                     html: "test2".to_string(),
                     tags: vec![Tag("post".to_string())],
                     reading_time: Duration::minutes(1),
-                    accessible: true,
+                    word_count: 0,
+                    excerpt: String::new(),
+                    plain_text: String::new(),
+                    status: PostStatus::Unlisted,
                     publication_date: None,
+                    updated_at: DateTime::UNIX_EPOCH,
+                    series: None,
+                    language: Language("de".to_string()),
+                    translation_key: Some("test".to_string()),
+                    renderer_version: 0,
+                    image_urls: HashMap::new(),
+                    pinned: false,
+                    featured: false,
+                    outline: Vec::new(),
+                    table_of_contents: false,
                 },
                 not_accessible: BlogPost {
                     url: "not_accessible".to_string(),
                     title: "Test (Not Accessible)".to_string(),
-                    description: "No description".to_string(),
-                    author: Author("Quiet".to_string()),
+                    description: Some("No description".to_string()),
+                    authors: vec![Author("Quiet".to_string())],
                     markdown: Some("test3".to_string()),
                     html: "test3".to_string(),
                     tags: vec![Tag("post".to_string())],
                     reading_time: Duration::minutes(1),
-                    accessible: false,
+                    word_count: 0,
+                    excerpt: String::new(),
+                    plain_text: String::new(),
+                    status: PostStatus::Draft,
                     publication_date: None,
+                    updated_at: DateTime::UNIX_EPOCH,
+                    series: None,
+                    language: Language("en-gb".to_string()),
+                    translation_key: None,
+                    renderer_version: 0,
+                    image_urls: HashMap::new(),
+                    pinned: false,
+                    featured: false,
+                    outline: Vec::new(),
+                    table_of_contents: false,
                 },
                 public_in_future: BlogPost {
                     url: "public_in_future".to_string(),
                     title: "Test (Public in future)".to_string(),
-                    description: "No description".to_string(),
-                    author: Author("Quiet".to_string()),
+                    description: Some("No description".to_string()),
+                    authors: vec![Author("Quiet".to_string())],
                     markdown: Some("test4".to_string()),
                     html: "test4".to_string(),
                     tags: vec![Tag("post".to_string())],
                     reading_time: Duration::minutes(1),
-                    accessible: false,
+                    word_count: 0,
+                    excerpt: String::new(),
+                    plain_text: String::new(),
+                    status: PostStatus::Scheduled,
                     publication_date: Some(DateTime::from_timestamp(10_000_000_000, 0).unwrap()),
+                    updated_at: DateTime::UNIX_EPOCH,
+                    series: None,
+                    language: Language("en-gb".to_string()),
+                    translation_key: None,
+                    renderer_version: 0,
+                    image_urls: HashMap::new(),
+                    pinned: false,
+                    featured: false,
+                    outline: Vec::new(),
+                    table_of_contents: false,
                 },
                 accessible_public_in_future: BlogPost {
                     url: "accessible_public_in_future".to_string(),
                     title: "Test (Accessible, Public in future)".to_string(),
-                    description: "No description".to_string(),
-                    author: Author("Quiet".to_string()),
+                    description: Some("No description".to_string()),
+                    authors: vec![Author("Quiet".to_string())],
                     markdown: Some("test5".to_string()),
                     html: "test5".to_string(),
                     tags: vec![Tag("post".to_string())],
                     reading_time: Duration::minutes(1),
-                    accessible: true,
+                    word_count: 0,
+                    excerpt: String::new(),
+                    plain_text: String::new(),
+                    status: PostStatus::Scheduled,
                     publication_date: Some(DateTime::from_timestamp(10_000_000_001, 0).unwrap()),
+                    updated_at: DateTime::UNIX_EPOCH,
+                    series: None,
+                    language: Language("en-gb".to_string()),
+                    translation_key: None,
+                    renderer_version: 0,
+                    image_urls: HashMap::new(),
+                    pinned: false,
+                    featured: false,
+                    outline: Vec::new(),
+                    table_of_contents: false,
                 },
                 long_post: BlogPost {
                     url: "long_post".to_string(),
                     title: "Test (Longer blog post)".to_string(),
-                    description: "No description".to_string(),
-                    author: Author("gpluscb".to_string()),
+                    description: Some("No description".to_string()),
+                    authors: vec![Author("gpluscb".to_string()), Author("Quiet".to_string())],
                     markdown: Some(include_str!("../test_fixtures/lorem.txt").to_string()),
                     html: include_str!("../test_fixtures/lorem.txt").to_string(),
                     tags: vec![
@@ -417,13 +2094,26 @@ mod tests {
                         Tag("public".to_string()),
                     ],
                     reading_time: Duration::minutes(60),
-                    accessible: true,
+                    word_count: 0,
+                    excerpt: String::new(),
+                    plain_text: String::new(),
+                    status: PostStatus::Published,
                     publication_date: Some(DateTime::from_timestamp(1, 0).unwrap()),
+                    updated_at: DateTime::UNIX_EPOCH,
+                    series: None,
+                    language: Language("en-gb".to_string()),
+                    translation_key: None,
+                    renderer_version: 0,
+                    image_urls: HashMap::new(),
+                    pinned: false,
+                    featured: false,
+                    outline: Vec::new(),
+                    table_of_contents: false,
                 },
             }
         }
 
-        /// In the order they should be returned in by `super::get_blog_posts(None, None, false, db)`.
+        /// In the order they should be returned in by `super::get_blog_posts(None, None, None, false, db)`.
         fn all(&self) -> [&BlogPost; 6] {
             [
                 &self.accessible_public_in_future,
@@ -439,14 +2129,22 @@ mod tests {
             &'a self,
             authors: Option<&'a [Author]>,
             tags: Option<&'a [Tag]>,
+            languages: Option<&'a [Language]>,
             published_only: bool,
         ) -> impl IntoIterator<Item = &'a BlogPost> + 'a {
             self.all()
                 .into_iter()
                 .filter(move |post| !published_only || post.is_public())
-                .filter(move |post| authors.map_or(true, |authors| authors.contains(&post.author)))
                 .filter(move |post| {
-                    tags.map_or(true, |tags| tags.iter().any(|tag| post.tags.contains(tag)))
+                    authors.is_none_or(|authors| {
+                        authors.iter().any(|author| post.authors.contains(author))
+                    })
+                })
+                .filter(move |post| {
+                    tags.is_none_or(|tags| tags.iter().any(|tag| post.tags.contains(tag)))
+                })
+                .filter(move |post| {
+                    languages.is_none_or(|languages| languages.contains(&post.language))
                 })
         }
     }
@@ -520,7 +2218,7 @@ mod tests {
         let all_tested_authors: Vec<_> = expected_blog_posts
             .all()
             .into_iter()
-            .map(|post| &post.author)
+            .flat_map(|post| &post.authors)
             .unique()
             .cloned()
             .chain(std::iter::once(Author("Unknown".to_string())))
@@ -541,33 +2239,236 @@ mod tests {
 
         let tested_tag_params = tested_filter_parameters(all_tested_tags);
 
+        // Languages are tested less exhaustively to keep the number of queries down
+        let tested_language_params = [
+            None,
+            Some(vec![Language("de".to_string())]),
+            Some(vec![
+                Language("en-gb".to_string()),
+                Language("Unknown".to_string()),
+            ]),
+        ];
+
         let checked_public_flags = [true, false];
 
         for public_flag in checked_public_flags {
             for author_params in &tested_author_params {
                 for tag_params in &tested_tag_params {
-                    let blog_posts = super::get_blog_posts(
-                        author_params.as_deref(),
-                        tag_params.as_deref(),
-                        public_flag,
+                    for language_params in &tested_language_params {
+                        let blog_posts = super::get_blog_posts(
+                            author_params.as_deref(),
+                            tag_params.as_deref(),
+                            language_params.as_deref(),
+                            public_flag,
+                            PostSelection::All,
+                            &pool,
+                        )
+                        .await?;
+
+                        let expected = expected_blog_posts.get_expected_for_params(
+                            author_params.as_deref(),
+                            tag_params.as_deref(),
+                            language_params.as_deref(),
+                            public_flag,
+                        );
+
+                        itertools::assert_equal(&blog_posts, expected);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn get_blog_posts_page_test(pool: PgPool) -> super::Result<()> {
+        // Posts that only differ in their url still get separate pages
+        let mut transaction = pool.begin().await?;
+        for url in ["twin_b", "twin_a"] {
+            let twin = BlogPost {
+                url: url.to_string(),
+                translation_key: None,
+                ..ExpectedBlogPosts::new().public
+            };
+            super::insert_blog_post(&twin, &mut transaction).await?;
+        }
+        transaction.commit().await?;
+
+        for published_only in [true, false] {
+            let all =
+                super::get_blog_posts(None, None, None, published_only, PostSelection::All, &pool)
+                    .await?;
+
+            for size in [1, 2, 4, 10] {
+                // Walk forwards through all pages
+                let mut pages = Vec::new();
+                let mut boundary = None;
+                loop {
+                    let page = super::get_blog_posts_page(
+                        None,
+                        None,
+                        None,
+                        published_only,
+                        PostSelection::All,
+                        boundary.as_ref(),
+                        size,
                         &pool,
                     )
                     .await?;
 
-                    let expected = expected_blog_posts.get_expected_for_params(
-                        author_params.as_deref(),
-                        tag_params.as_deref(),
-                        public_flag,
-                    );
+                    assert!(page.blog_posts.len() <= size as usize);
+                    assert_eq!(page.previous.is_some(), !pages.is_empty());
+
+                    boundary = page.next.clone().map(PageBoundary::After);
+                    pages.push(page.blog_posts);
+
+                    if boundary.is_none() {
+                        break;
+                    }
+                }
+
+                itertools::assert_equal(pages.iter().flatten(), &all);
+
+                // And backwards again, which should give the same pages
+                let mut previous =
+                    (pages.len() > 1).then(|| PostKey::of(&pages.last().unwrap()[0]));
+                for expected in pages.iter().rev().skip(1) {
+                    let boundary = previous.map(PageBoundary::Before);
+                    let page = super::get_blog_posts_page(
+                        None,
+                        None,
+                        None,
+                        published_only,
+                        PostSelection::All,
+                        boundary.as_ref(),
+                        size,
+                        &pool,
+                    )
+                    .await?;
 
-                    itertools::assert_equal(&blog_posts, expected);
+                    assert_eq!(&page.blog_posts, expected);
+                    assert!(page.next.is_some());
+                    previous = page.previous;
                 }
+                assert_eq!(previous, None);
             }
         }
 
         Ok(())
     }
 
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn pinned_and_featured_test(pool: PgPool) -> super::Result<()> {
+        let expected_blog_posts = ExpectedBlogPosts::new();
+        let pinned = BlogPost {
+            pinned: true,
+            ..expected_blog_posts.public.clone()
+        };
+        let featured = BlogPost {
+            featured: true,
+            ..expected_blog_posts.long_post.clone()
+        };
+
+        let mut transaction = pool.begin().await?;
+        for post in [&pinned, &featured] {
+            super::update_blog_post(None, post, &post.authors[0], &mut transaction).await?;
+        }
+        transaction.commit().await?;
+
+        let pinned_first = ignoring_update_times([
+            pinned.clone(),
+            expected_blog_posts.accessible_public_in_future.clone(),
+            expected_blog_posts.public_in_future.clone(),
+            featured.clone(),
+            expected_blog_posts.accessible.clone(),
+            expected_blog_posts.not_accessible.clone(),
+        ]);
+        assert_eq!(
+            ignoring_update_times(
+                super::get_blog_posts(None, None, None, false, PostSelection::PinnedFirst, &pool)
+                    .await?
+            ),
+            pinned_first,
+        );
+
+        // Pages follow the same order in both directions
+        let mut boundary = None;
+        for expected in &pinned_first {
+            let page = super::get_blog_posts_page(
+                None,
+                None,
+                None,
+                false,
+                PostSelection::PinnedFirst,
+                boundary.as_ref(),
+                1,
+                &pool,
+            )
+            .await?;
+            assert_eq!(
+                ignoring_update_times(page.blog_posts),
+                std::slice::from_ref(expected)
+            );
+            boundary = page.next.map(PageBoundary::After);
+        }
+        assert_eq!(boundary, None);
+
+        let mut boundary = Some(PageBoundary::Before(PostKey::of(&pinned_first[5])));
+        for expected in pinned_first.iter().rev().skip(1) {
+            let page = super::get_blog_posts_page(
+                None,
+                None,
+                None,
+                false,
+                PostSelection::PinnedFirst,
+                boundary.as_ref(),
+                1,
+                &pool,
+            )
+            .await?;
+            assert_eq!(
+                ignoring_update_times(page.blog_posts),
+                std::slice::from_ref(expected)
+            );
+            boundary = page.previous.map(PageBoundary::Before);
+        }
+        assert_eq!(boundary, None);
+
+        // Pinning does not affect the default order
+        assert_eq!(
+            ignoring_update_times(
+                super::get_blog_posts(None, None, None, true, PostSelection::All, &pool).await?
+            ),
+            ignoring_update_times([featured.clone(), pinned.clone()]),
+        );
+        assert_eq!(
+            ignoring_update_times(
+                super::get_blog_posts(None, None, None, false, PostSelection::FeaturedOnly, &pool)
+                    .await?
+            ),
+            ignoring_update_times([featured]),
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn post_key_test() {
+        for publication_date in [None, Some(DateTime::from_timestamp(1, 0).unwrap())] {
+            for pinned in [false, true] {
+                let key = PostKey {
+                    pinned,
+                    publication_date,
+                    title: "Test ~ title".to_string(),
+                    url: "test~%7E-url".to_string(),
+                };
+
+                assert_eq!(PostKey::try_from(String::from(key.clone())), Ok(key));
+            }
+        }
+    }
+
     #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
     async fn tags_test(pool: PgPool) -> super::Result<()> {
         let expected_blog_posts = ExpectedBlogPosts::new();
@@ -586,5 +2487,964 @@ mod tests {
         Ok(())
     }
 
-    // TODO: Add insert/update tests
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn revisions_test(pool: PgPool) -> super::Result<()> {
+        let original = BlogPost {
+            url: "revised".to_string(),
+            title: "Test (Revised)".to_string(),
+            description: Some("No description".to_string()),
+            authors: vec![Author("Quiet".to_string())],
+            markdown: Some("test6".to_string()),
+            html: "test6".to_string(),
+            tags: vec![Tag("post".to_string())],
+            reading_time: Duration::minutes(1),
+            word_count: 0,
+            excerpt: String::new(),
+            plain_text: String::new(),
+            status: PostStatus::Draft,
+            publication_date: None,
+            updated_at: DateTime::UNIX_EPOCH,
+            series: None,
+            language: Language("en-gb".to_string()),
+            translation_key: None,
+            renderer_version: 0,
+            image_urls: HashMap::new(),
+            pinned: false,
+            featured: false,
+            outline: Vec::new(),
+            table_of_contents: false,
+        };
+        let updated = BlogPost {
+            title: "Test (Revised, Updated)".to_string(),
+            markdown: Some("test6 *updated*".to_string()),
+            html: "test6 <b>updated</b>".to_string(),
+            status: PostStatus::Unlisted,
+            ..original.clone()
+        };
+        let editor = Author("gpluscb".to_string());
+
+        let mut transaction = pool.begin().await?;
+        super::insert_blog_post(&original, &mut transaction).await?;
+        transaction.commit().await?;
+
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(None, &updated, &editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        let revisions = super::get_blog_post_revisions(&original.url, &pool).await?;
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].post.clone().ignoring_update_time(), original);
+        assert_eq!(revisions[0].changed_by, original.authors[0]);
+        assert_eq!(revisions[1].post.clone().ignoring_update_time(), updated);
+        assert!(revisions[1].post.updated_at > revisions[0].post.updated_at);
+        assert_eq!(revisions[1].changed_by, editor);
+
+        // Revisions of other posts can't be used for a rollback
+        let mut transaction = pool.begin().await?;
+        assert!(super::rollback_blog_post(
+            "public",
+            revisions[0].revision,
+            &editor,
+            &mut transaction
+        )
+        .await
+        .is_err());
+        transaction.rollback().await?;
+
+        let mut transaction = pool.begin().await?;
+        super::rollback_blog_post(
+            &original.url,
+            revisions[0].revision,
+            &editor,
+            &mut transaction,
+        )
+        .await?;
+        transaction.commit().await?;
+
+        // Rollbacks count as a change
+        let rolled_back = super::get_blog_post(&original.url, false, &pool)
+            .await?
+            .unwrap();
+        assert!(rolled_back.updated_at > revisions[1].post.updated_at);
+        assert_eq!(
+            rolled_back,
+            BlogPost {
+                updated_at: rolled_back.updated_at,
+                ..original.clone()
+            },
+        );
+
+        let revisions = super::get_blog_post_revisions(&original.url, &pool).await?;
+        assert_eq!(revisions.len(), 3);
+        assert_eq!(revisions[2].post, rolled_back);
+        assert_eq!(revisions[2].changed_by, editor);
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn delete_archive_unpublish_test(pool: PgPool) -> super::Result<()> {
+        let expected_blog_posts = ExpectedBlogPosts::new();
+        let editor = Author("gpluscb".to_string());
+
+        // Archived posts are still listed
+        let mut transaction = pool.begin().await?;
+        super::archive_blog_post("public", true, &editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        // Archiving leaves the contents and with them the update time alone
+        let archived = super::get_blog_post("public", true, &pool).await?.unwrap();
+        assert_eq!(
+            archived,
+            BlogPost {
+                status: PostStatus::Archived,
+                ..expected_blog_posts.public.clone()
+            },
+        );
+        assert!(
+            super::get_blog_posts(None, None, None, true, PostSelection::All, &pool)
+                .await?
+                .contains(&archived)
+        );
+
+        let mut transaction = pool.begin().await?;
+        super::archive_blog_post("public", false, &editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(
+            super::get_blog_post("public", true, &pool).await?.as_ref(),
+            Some(&expected_blog_posts.public)
+        );
+
+        // Only published posts can be archived
+        let mut transaction = pool.begin().await?;
+        assert!(matches!(
+            super::archive_blog_post("accessible", true, &editor, &mut transaction).await,
+            Err(super::Error::InvalidInput)
+        ));
+        transaction.rollback().await?;
+
+        // Unpublished posts are no longer accessible
+        let mut transaction = pool.begin().await?;
+        super::unpublish_blog_post("long_post", &editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(super::get_blog_post("long_post", true, &pool).await?, None);
+        assert_eq!(
+            super::get_blog_post_revisions("long_post", &pool)
+                .await?
+                .last()
+                .map(|revision| &revision.changed_by),
+            Some(&editor),
+        );
+
+        // Deleted posts are gone, including their tags, but their history is kept
+        let revisions = super::get_blog_post_revisions("long_post", &pool).await?;
+        let mut transaction = pool.begin().await?;
+        super::delete_blog_post("long_post", &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(super::get_blog_post("long_post", false, &pool).await?, None);
+        assert_eq!(
+            super::get_blog_post_revisions("long_post", &pool).await?,
+            revisions
+                .into_iter()
+                .map(|revision| BlogPostRevision {
+                    post_url: None,
+                    ..revision
+                })
+                .collect_vec()
+        );
+        assert!(!super::get_tags(false, &pool)
+            .await?
+            .contains(&Tag("lorem-ipsum".to_string())));
+
+        let mut transaction = pool.begin().await?;
+        assert!(super::delete_blog_post("long_post", &mut transaction)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors")))]
+    async fn authors_test(pool: PgPool) -> super::Result<()> {
+        // Fixture authors have no profile data yet
+        let quiet = super::get_author(&Author("Quiet".to_string()), &pool)
+            .await?
+            .unwrap();
+        assert_eq!(
+            quiet,
+            AuthorProfile {
+                author: Author("Quiet".to_string()),
+                display_name: None,
+                bio_markdown: None,
+                bio_html: None,
+                avatar_path: None,
+                links: Vec::new(),
+            }
+        );
+        assert_eq!(quiet.name(), "Quiet");
+        assert_eq!(
+            super::get_author(&Author("nobody".to_string()), &pool).await?,
+            None
+        );
+
+        let mut profile = AuthorProfile {
+            author: Author("new".to_string()),
+            display_name: Some("New Author".to_string()),
+            bio_markdown: Some("Hi".to_string()),
+            bio_html: Some("<p>Hi</p>".to_string()),
+            avatar_path: Some("/avatars/new.png".to_string()),
+            links: vec![
+                AuthorLink {
+                    label: "Website".to_string(),
+                    url: "https://example.com".to_string(),
+                },
+                AuthorLink {
+                    label: "Code".to_string(),
+                    url: "https://example.com/code".to_string(),
+                },
+            ],
+        };
+
+        let mut transaction = pool.begin().await?;
+        super::insert_author(&profile, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(
+            super::get_author(&profile.author, &pool).await?.as_ref(),
+            Some(&profile)
+        );
+        assert_eq!(profile.name(), "New Author");
+
+        // Updating replaces links
+        profile.display_name = None;
+        profile.links.remove(0);
+
+        let mut transaction = pool.begin().await?;
+        super::update_author(&profile, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(
+            super::get_author(&profile.author, &pool).await?.as_ref(),
+            Some(&profile)
+        );
+
+        let authors = super::get_authors(&pool)
+            .await?
+            .into_iter()
+            .map(|profile| profile.author.0)
+            .collect_vec();
+        assert_eq!(authors, vec!["Quiet", "gpluscb", "new"]);
+
+        // Updating an unknown author fails
+        let mut transaction = pool.begin().await?;
+        assert!(matches!(
+            super::update_author(
+                &AuthorProfile {
+                    author: Author("nobody".to_string()),
+                    ..profile.clone()
+                },
+                &mut transaction
+            )
+            .await,
+            Err(super::Error::InvalidInput)
+        ));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn post_status_test(pool: PgPool) -> super::Result<()> {
+        let editor = Author("gpluscb".to_string());
+        let past = Some(DateTime::UNIX_EPOCH);
+
+        for (status, publication_date, accessible, public) in [
+            (PostStatus::Scheduled, past, true, true),
+            (PostStatus::Published, past, true, true),
+            (PostStatus::Archived, past, true, true),
+            (PostStatus::Draft, None, false, false),
+            (PostStatus::Unlisted, None, true, false),
+        ] {
+            let post = BlogPost {
+                status,
+                publication_date,
+                ..ExpectedBlogPosts::new().public
+            };
+
+            let mut transaction = pool.begin().await?;
+            super::update_blog_post(None, &post, &editor, &mut transaction).await?;
+            transaction.commit().await?;
+
+            assert_eq!(post.is_accessible_or_public(), accessible);
+            assert_eq!(post.is_public(), public);
+            assert_eq!(
+                super::get_blog_post("public", true, &pool).await?.is_some(),
+                accessible,
+                "{status} post accessibility",
+            );
+            assert_eq!(
+                ignoring_update_times(
+                    super::get_blog_posts(None, None, None, true, PostSelection::All, &pool)
+                        .await?
+                )
+                .contains(&post.clone().ignoring_update_time()),
+                public,
+                "{status} post listing",
+            );
+        }
+
+        // Only released statuses have a publication date
+        for (status, publication_date) in [
+            (PostStatus::Draft, past),
+            (PostStatus::Unlisted, past),
+            (PostStatus::Scheduled, None),
+            (PostStatus::Published, None),
+            (PostStatus::Archived, None),
+        ] {
+            let post = BlogPost {
+                status,
+                publication_date,
+                ..ExpectedBlogPosts::new().public
+            };
+
+            let mut transaction = pool.begin().await?;
+            assert!(
+                super::update_blog_post(None, &post, &editor, &mut transaction)
+                    .await
+                    .is_err()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn url_redirect_test(pool: PgPool) -> super::Result<()> {
+        let post = BlogPost {
+            url: "first".to_string(),
+            title: "Test (Renamed)".to_string(),
+            description: Some("No description".to_string()),
+            authors: vec![Author("Quiet".to_string())],
+            markdown: Some("test7".to_string()),
+            html: "test7".to_string(),
+            tags: Vec::new(),
+            reading_time: Duration::minutes(1),
+            word_count: 0,
+            excerpt: String::new(),
+            plain_text: String::new(),
+            status: PostStatus::Unlisted,
+            publication_date: None,
+            updated_at: DateTime::UNIX_EPOCH,
+            series: None,
+            language: Language("en-gb".to_string()),
+            translation_key: None,
+            renderer_version: 0,
+            image_urls: HashMap::new(),
+            pinned: false,
+            featured: false,
+            outline: Vec::new(),
+            table_of_contents: false,
+        };
+        let editor = &post.authors[0];
+
+        let mut transaction = pool.begin().await?;
+        super::insert_blog_post(&post, &mut transaction).await?;
+        transaction.commit().await?;
+
+        for (original_url, url) in [("first", "second"), ("second", "third")] {
+            let renamed = BlogPost {
+                url: url.to_string(),
+                ..post.clone()
+            };
+
+            let mut transaction = pool.begin().await?;
+            super::update_blog_post(Some(original_url), &renamed, editor, &mut transaction).await?;
+            transaction.commit().await?;
+        }
+
+        // Chains of renames resolve to the current url
+        for old_url in ["first", "second"] {
+            assert_eq!(
+                super::get_url_redirect(old_url, &pool).await?.as_deref(),
+                Some("third"),
+            );
+        }
+        assert_eq!(super::get_url_redirect("third", &pool).await?, None);
+
+        // Renaming back to an old url removes the redirect for that url
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(Some("third"), &post, editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(super::get_url_redirect("first", &pool).await?, None);
+        for old_url in ["second", "third"] {
+            assert_eq!(
+                super::get_url_redirect(old_url, &pool).await?.as_deref(),
+                Some("first"),
+            );
+        }
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn preview_token_test(pool: PgPool) -> super::Result<()> {
+        let editor = Author("gpluscb".to_string());
+
+        let mut transaction = pool.begin().await?;
+        let first =
+            super::create_preview_token("not_accessible", &editor, &mut transaction).await?;
+        let second =
+            super::create_preview_token("not_accessible", &editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_ne!(first.token, second.token);
+        assert_eq!(first.post_url, "not_accessible");
+        assert_eq!(first.created_by, editor);
+        // Both were created in the same transaction, so they are ordered by token
+        assert_eq!(
+            super::get_preview_tokens("not_accessible", &pool).await?,
+            [first.clone(), second.clone()]
+                .into_iter()
+                .sorted_by(|a, b| a.token.cmp(&b.token))
+                .collect_vec(),
+        );
+
+        // Tokens are only valid for their own post
+        assert!(super::is_valid_preview_token("not_accessible", &first.token, &pool).await?);
+        assert!(!super::is_valid_preview_token("public", &first.token, &pool).await?);
+        assert!(!super::is_valid_preview_token("not_accessible", "guess", &pool).await?);
+
+        // Revoked tokens are no longer valid
+        let mut transaction = pool.begin().await?;
+        super::revoke_preview_token(&first.token, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert!(!super::is_valid_preview_token("not_accessible", &first.token, &pool).await?);
+        assert!(super::is_valid_preview_token("not_accessible", &second.token, &pool).await?);
+
+        let mut transaction = pool.begin().await?;
+        assert!(matches!(
+            super::revoke_preview_token(&first.token, &mut transaction).await,
+            Err(super::Error::InvalidInput)
+        ));
+        transaction.rollback().await?;
+
+        // Tokens follow url changes
+        let renamed = BlogPost {
+            url: "renamed".to_string(),
+            ..ExpectedBlogPosts::new().not_accessible
+        };
+
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(Some("not_accessible"), &renamed, &editor, &mut transaction)
+            .await?;
+        transaction.commit().await?;
+
+        assert!(super::is_valid_preview_token("renamed", &second.token, &pool).await?);
+
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn media_asset_test(pool: PgPool) -> super::Result<()> {
+        let asset = MediaAsset::new(b"not really a png".to_vec(), "PNG").unwrap();
+        assert_eq!(asset.content_type, "image/png");
+        assert_eq!(asset.file_name(), format!("{}.png", asset.hash));
+
+        assert_eq!(super::get_media_asset(&asset.hash, &pool).await?, None);
+
+        // Inserting the same contents twice is fine
+        for _ in 0..2 {
+            let mut transaction = pool.begin().await?;
+            super::insert_media_asset(&asset, &mut transaction).await?;
+            transaction.commit().await?;
+        }
+
+        assert_eq!(
+            super::get_media_asset(&asset.hash, &pool).await?,
+            Some(asset)
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn series_test(pool: PgPool) -> super::Result<()> {
+        let series = Series("test-series".to_string());
+        let part = |url: &str, part: u32, publication_date: Option<_>| BlogPost {
+            url: url.to_string(),
+            title: format!("Test (Series part {part})"),
+            description: Some("No description".to_string()),
+            authors: vec![Author("Quiet".to_string())],
+            markdown: Some("test8".to_string()),
+            html: "test8".to_string(),
+            tags: Vec::new(),
+            reading_time: Duration::minutes(1),
+            word_count: 0,
+            excerpt: String::new(),
+            plain_text: String::new(),
+            status: publication_date.map_or(PostStatus::Draft, |_| PostStatus::Published),
+            publication_date,
+            updated_at: DateTime::UNIX_EPOCH,
+            series: Some(SeriesPart {
+                series: series.clone(),
+                part,
+            }),
+            language: Language("en-gb".to_string()),
+            translation_key: None,
+            renderer_version: 0,
+            image_urls: HashMap::new(),
+            pinned: false,
+            featured: false,
+            outline: Vec::new(),
+            table_of_contents: false,
+        };
+
+        let second = part(
+            "second_part",
+            2,
+            Some(DateTime::from_timestamp(2, 0).unwrap()),
+        );
+        let first = part(
+            "first_part",
+            1,
+            Some(DateTime::from_timestamp(3, 0).unwrap()),
+        );
+        let unpublished = part("unpublished_part", 3, None);
+
+        let mut transaction = pool.begin().await?;
+        for post in [&second, &first, &unpublished] {
+            super::insert_blog_post(post, &mut transaction).await?;
+        }
+        transaction.commit().await?;
+
+        assert_eq!(
+            ignoring_update_times(super::get_series_posts(&series, true, &pool).await?),
+            vec![first.clone(), second.clone()],
+        );
+        assert_eq!(
+            ignoring_update_times(super::get_series_posts(&series, false, &pool).await?),
+            vec![first.clone(), second.clone(), unpublished],
+        );
+
+        // Part numbers are unique within a series
+        let mut transaction = pool.begin().await?;
+        assert!(
+            super::insert_blog_post(&part("duplicate_part", 1, None), &mut transaction)
+                .await
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn translations_test(pool: PgPool) -> super::Result<()> {
+        let expected_blog_posts = ExpectedBlogPosts::new();
+
+        assert_eq!(
+            super::get_translations("test", false, &pool).await?,
+            vec![
+                expected_blog_posts.accessible.clone(),
+                expected_blog_posts.public.clone(),
+            ],
+        );
+        assert_eq!(
+            super::get_translations("test", true, &pool).await?,
+            vec![expected_blog_posts.public.clone()],
+        );
+
+        assert_eq!(
+            super::get_languages(false, &pool).await?,
+            vec![Language("de".to_string()), Language("en-gb".to_string())],
+        );
+        assert_eq!(
+            super::get_languages(true, &pool).await?,
+            vec![Language("en-gb".to_string())],
+        );
+
+        // There can only be one translation per language
+        let duplicate = BlogPost {
+            url: "duplicate_translation".to_string(),
+            ..expected_blog_posts.accessible.clone()
+        };
+        let mut transaction = pool.begin().await?;
+        assert!(super::insert_blog_post(&duplicate, &mut transaction)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn search_test(pool: PgPool) -> super::Result<()> {
+        let urls = |results: Vec<SearchResult>| {
+            results
+                .into_iter()
+                .map(|result| result.blog_post.url)
+                .collect::<Vec<_>>()
+        };
+
+        // Unpublished posts are only found without public filtering
+        assert_eq!(
+            urls(super::search_blog_posts("bold", true, &pool).await?),
+            vec!["public"],
+        );
+        assert_eq!(
+            urls(super::search_blog_posts("longer", true, &pool).await?),
+            vec!["long_post"],
+        );
+        assert_eq!(
+            urls(super::search_blog_posts("accessible", true, &pool).await?),
+            Vec::<String>::new(),
+        );
+        assert_eq!(
+            urls(super::search_blog_posts("accessible -future", false, &pool).await?),
+            vec!["accessible", "not_accessible"],
+        );
+
+        // Only the plain text of posts is searched, not the markdown with its front matter, and
+        // posts that aren't in English are not stemmed or stripped of English stop words
+        let german = BlogPost {
+            url: "german".to_string(),
+            markdown: Some("+++\n{\"author\": \"Zebra\"}\n+++\n\nWas ist neu?".to_string()),
+            plain_text: "Was ist neu?".to_string(),
+            language: Language("de".to_string()),
+            translation_key: None,
+            ..ExpectedBlogPosts::new().public
+        };
+        let mut transaction = pool.begin().await?;
+        super::insert_blog_post(&german, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(
+            urls(super::search_blog_posts("zebra", true, &pool).await?),
+            Vec::<String>::new(),
+        );
+        assert_eq!(
+            urls(super::search_blog_posts("was", true, &pool).await?),
+            vec!["german"],
+        );
+
+        let results = super::search_blog_posts("bold", true, &pool).await?;
+        assert!(results[0]
+            .snippet
+            .iter()
+            .any(|fragment| fragment.highlighted && fragment.text == "bold"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn snippet_fragments_test() {
+        let fragment = |text: &str, highlighted| SnippetFragment {
+            text: text.to_string(),
+            highlighted,
+        };
+
+        assert_eq!(
+            super::snippet_fragments("a \u{2}b\u{3} c \u{2}d\u{3}"),
+            vec![
+                fragment("a ", false),
+                fragment("b", true),
+                fragment(" c ", false),
+                fragment("d", true),
+            ],
+        );
+        assert_eq!(
+            super::snippet_fragments("\u{2}a\u{3}"),
+            vec![fragment("a", true)]
+        );
+        assert_eq!(super::snippet_fragments("a"), vec![fragment("a", false)]);
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn insert_blog_post_test(pool: PgPool) -> super::Result<()> {
+        let post = BlogPost {
+            url: "new".to_string(),
+            title: "New".to_string(),
+            description: None,
+            tags: vec![Tag("new".to_string()), Tag("post".to_string())],
+            translation_key: None,
+            renderer_version: 1,
+            image_urls: HashMap::from([("image.png".to_string(), "/media/hash.png".to_string())]),
+            pinned: true,
+            featured: true,
+            outline: vec![Heading {
+                level: 2,
+                text: "Heading".to_string(),
+                id: Some("heading".to_string()),
+            }],
+            table_of_contents: true,
+            word_count: 321,
+            excerpt: "<p>New post</p>\n".to_string(),
+            plain_text: "New post".to_string(),
+            ..ExpectedBlogPosts::new().public
+        };
+
+        let mut transaction = pool.begin().await?;
+        super::insert_blog_post(&post, &mut transaction).await?;
+        transaction.commit().await?;
+
+        let inserted = super::get_blog_post("new", false, &pool).await?.unwrap();
+        assert!(inserted.updated_at > post.updated_at);
+        assert_eq!(
+            inserted,
+            BlogPost {
+                updated_at: inserted.updated_at,
+                ..post.clone()
+            }
+        );
+
+        // Urls are unique
+        let mut transaction = pool.begin().await?;
+        assert!(super::insert_blog_post(&post, &mut transaction)
+            .await
+            .is_err());
+
+        // Authors have to exist
+        let mut transaction = pool.begin().await?;
+        assert!(super::insert_blog_post(
+            &BlogPost {
+                url: "unknown_author".to_string(),
+                authors: vec![Author("nobody".to_string())],
+                ..post.clone()
+            },
+            &mut transaction
+        )
+        .await
+        .is_err());
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn update_blog_post_test(pool: PgPool) -> super::Result<()> {
+        let editor = &Author("gpluscb".to_string());
+        let original = ExpectedBlogPosts::new().long_post;
+        assert_eq!(
+            original.tags,
+            vec![
+                Tag("lorem-ipsum".to_string()),
+                Tag("post".to_string()),
+                Tag("public".to_string()),
+            ]
+        );
+
+        // Adding and removing tags at the same time
+        let retagged = BlogPost {
+            title: "Retagged".to_string(),
+            tags: vec![Tag("new".to_string()), Tag("post".to_string())],
+            ..original.clone()
+        };
+
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(None, &retagged, editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(
+            super::get_blog_post("long_post", false, &pool)
+                .await?
+                .map(BlogPost::ignoring_update_time)
+                .as_ref(),
+            Some(&retagged)
+        );
+
+        assert!(super::get_blog_post("long_post", false, &pool)
+            .await?
+            .is_some_and(|post| post.updated_at > original.updated_at));
+
+        // Removed tags are gone from the tag listing as well
+        let tags = super::get_tags(false, &pool).await?;
+        assert!(!tags.contains(&Tag("lorem-ipsum".to_string())));
+        assert!(tags.contains(&Tag("public".to_string())));
+
+        // Changing the url keeps the tags attached
+        let renamed = BlogPost {
+            url: "renamed".to_string(),
+            ..retagged.clone()
+        };
+
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(Some("long_post"), &renamed, editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(super::get_blog_post("long_post", false, &pool).await?, None);
+        assert_eq!(
+            super::get_blog_post("renamed", false, &pool)
+                .await?
+                .map(BlogPost::ignoring_update_time)
+                .as_ref(),
+            Some(&renamed)
+        );
+
+        // Changing the url and the tags together
+        let renamed_retagged = BlogPost {
+            url: "renamed_again".to_string(),
+            tags: vec![Tag("lorem-ipsum".to_string())],
+            ..renamed.clone()
+        };
+
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(Some("renamed"), &renamed_retagged, editor, &mut transaction)
+            .await?;
+        transaction.commit().await?;
+
+        assert_eq!(
+            super::get_blog_post("renamed_again", false, &pool)
+                .await?
+                .map(BlogPost::ignoring_update_time)
+                .as_ref(),
+            Some(&renamed_retagged)
+        );
+
+        // Removing all tags
+        let untagged = BlogPost {
+            tags: Vec::new(),
+            ..renamed_retagged.clone()
+        };
+
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(None, &untagged, editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(
+            super::get_blog_post("renamed_again", false, &pool)
+                .await?
+                .map(BlogPost::ignoring_update_time)
+                .as_ref(),
+            Some(&untagged)
+        );
+
+        // Replacing and reordering authors
+        let reauthored = BlogPost {
+            authors: vec![Author("Quiet".to_string()), Author("gpluscb".to_string())],
+            ..untagged.clone()
+        };
+
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(None, &reauthored, editor, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(
+            super::get_blog_post("renamed_again", false, &pool)
+                .await?
+                .map(BlogPost::ignoring_update_time)
+                .as_ref(),
+            Some(&reauthored)
+        );
+
+        // Posts need at least one author
+        let mut transaction = pool.begin().await?;
+        assert!(matches!(
+            super::update_blog_post(
+                None,
+                &BlogPost {
+                    authors: Vec::new(),
+                    ..reauthored.clone()
+                },
+                editor,
+                &mut transaction
+            )
+            .await,
+            Err(super::Error::InvalidInput)
+        ));
+        transaction.rollback().await?;
+
+        // Updating a post that does not exist
+        let mut transaction = pool.begin().await?;
+        assert!(matches!(
+            super::update_blog_post(Some("does_not_exist"), &untagged, editor, &mut transaction)
+                .await,
+            Err(super::Error::InvalidInput)
+        ));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn update_rendered_blog_post_test(pool: PgPool) -> super::Result<()> {
+        let original = super::get_blog_post("public", false, &pool)
+            .await?
+            .expect("public is in the fixtures");
+        let revisions = super::get_blog_post_revisions("public", &pool).await?;
+
+        let rerendered = BlogPost {
+            html: "<p>Rerendered</p>".to_string(),
+            word_count: 1,
+            excerpt: "<p>Rerendered</p>".to_string(),
+            plain_text: "Rerendered".to_string(),
+            renderer_version: RENDERER_VERSION,
+            ..original.clone()
+        };
+        pool.update_rendered_blog_post(&rerendered).await?;
+
+        // Rerendering changes neither the update time nor the history
+        assert_eq!(
+            super::get_blog_post("public", false, &pool).await?,
+            Some(rerendered)
+        );
+        assert_eq!(
+            super::get_blog_post_revisions("public", &pool).await?.len(),
+            revisions.len()
+        );
+
+        assert!(matches!(
+            pool.update_rendered_blog_post(&BlogPost {
+                url: "does_not_exist".to_string(),
+                ..original
+            })
+            .await,
+            Err(super::Error::InvalidInput)
+        ));
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn next_publication_date_test(pool: PgPool) -> super::Result<()> {
+        let next = DateTime::from_timestamp(10_000_000_000, 0);
+        let after_next = DateTime::from_timestamp(10_000_000_001, 0);
+
+        assert_eq!(
+            super::get_next_publication_date(DateTime::from_timestamp(1, 0).unwrap(), &pool)
+                .await?,
+            next,
+        );
+        assert_eq!(
+            super::get_next_publication_date(next.unwrap(), &pool).await?,
+            after_next,
+        );
+        assert_eq!(
+            super::get_next_publication_date(after_next.unwrap(), &pool).await?,
+            None,
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../test_fixtures", scripts("authors", "blog_posts", "tags")))]
+    async fn blog_post_changed_test(pool: PgPool) -> super::Result<()> {
+        let mut listener = PgListener::connect_with(&pool).await?;
+        listener.listen(super::BLOG_POST_CHANGED_CHANNEL).await?;
+
+        let mut transaction = pool.begin().await?;
+        super::archive_blog_post(
+            "public",
+            true,
+            &Author("gpluscb".to_string()),
+            &mut transaction,
+        )
+        .await?;
+        super::delete_blog_post("long_post", &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(listener.recv().await?.payload(), "public");
+        assert_eq!(listener.recv().await?.payload(), "long_post");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,582 @@
+//! In-memory backend for route tests and CLI dry runs, nothing is persisted.
+//!
+//! Filtering and ordering mirror the Postgres queries. The helpers for that are shared with the
+//! SQLite backend, which does its filtering in Rust as well.
+
+use super::{
+    page_from_posts, Author, BlogStore, Error, Language, Page, PageBoundary, PostKey,
+    PostSelection, Result, Series, Tag,
+};
+use crate::blog::{AuthorProfile, BlogPost, SearchResult, SnippetFragment};
+use crate::media::MediaAsset;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+#[derive(Default, Debug)]
+pub struct MemoryStore {
+    state: Mutex<State>,
+}
+
+#[derive(Default, Debug)]
+struct State {
+    blog_posts: BTreeMap<String, BlogPost>,
+    authors: BTreeMap<Author, AuthorProfile>,
+    url_redirects: HashMap<String, String>,
+    /// Pairs of post url and token.
+    preview_tokens: HashSet<(String, String)>,
+    media_assets: HashMap<String, MediaAsset>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // Every operation leaves the state consistent before it could panic
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn insert_author(&self, author_profile: AuthorProfile) {
+        self.state()
+            .authors
+            .insert(author_profile.author.clone(), author_profile);
+    }
+
+    pub fn insert_preview_token(&self, url: &str, token: &str) {
+        self.state()
+            .preview_tokens
+            .insert((url.to_string(), token.to_string()));
+    }
+}
+
+/// Same order as [`super::get_blog_posts`]: pinned posts first if `selection` asks for it, then
+/// newest first, unpublished posts last, then by title and url.
+fn compare_keys(a: &PostKey, b: &PostKey, selection: PostSelection) -> Ordering {
+    let by_pinned = match selection {
+        PostSelection::PinnedFirst => b.pinned.cmp(&a.pinned),
+        PostSelection::All | PostSelection::FeaturedOnly => Ordering::Equal,
+    };
+    let by_date = match (a.publication_date, b.publication_date) {
+        (Some(a_date), Some(b_date)) => b_date.cmp(&a_date),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+
+    by_pinned
+        .then(by_date)
+        .then_with(|| a.title.cmp(&b.title))
+        .then_with(|| a.url.cmp(&b.url))
+}
+
+/// Filters like [`super::get_blog_posts`] and sorts the result.
+pub(super) fn filter_blog_posts(
+    blog_posts: impl IntoIterator<Item = BlogPost>,
+    authors: Option<&[Author]>,
+    tags: Option<&[Tag]>,
+    languages: Option<&[Language]>,
+    published_only: bool,
+    selection: PostSelection,
+) -> Vec<BlogPost> {
+    blog_posts
+        .into_iter()
+        .filter(|post| {
+            (!published_only || post.is_public())
+                && (selection != PostSelection::FeaturedOnly || post.featured)
+                && authors.is_none_or(|authors| {
+                    post.authors.iter().any(|author| authors.contains(author))
+                })
+                && tags.is_none_or(|tags| post.tags.iter().any(|tag| tags.contains(tag)))
+                && languages.is_none_or(|languages| languages.contains(&post.language))
+        })
+        .sorted_by(|a, b| compare_keys(&PostKey::of(a), &PostKey::of(b), selection))
+        .collect()
+}
+
+/// Picks the page starting at `boundary` out of all posts of the list, in list order.
+pub(super) fn page_of(
+    blog_posts: Vec<BlogPost>,
+    selection: PostSelection,
+    boundary: Option<&PageBoundary>,
+    size: u32,
+) -> Page {
+    let is_beyond = |post: &BlogPost, key: &PostKey, ordering: Ordering| {
+        compare_keys(&PostKey::of(post), key, selection) == ordering
+    };
+
+    // Take one more post than requested to know whether there are more pages
+    let page_size = size as usize + 1;
+    let blog_posts = match boundary {
+        None => blog_posts.into_iter().take(page_size).collect(),
+        Some(PageBoundary::After(key)) => blog_posts
+            .into_iter()
+            .filter(|post| is_beyond(post, key, Ordering::Greater))
+            .take(page_size)
+            .collect(),
+        Some(PageBoundary::Before(key)) => {
+            let mut before: Vec<_> = blog_posts
+                .into_iter()
+                .filter(|post| is_beyond(post, key, Ordering::Less))
+                .collect();
+            before.split_off(before.len().saturating_sub(page_size))
+        }
+    };
+
+    page_from_posts(blog_posts, boundary, size)
+}
+
+pub(super) fn series_posts(
+    blog_posts: impl IntoIterator<Item = BlogPost>,
+    series: &Series,
+    published_only: bool,
+) -> Vec<BlogPost> {
+    blog_posts
+        .into_iter()
+        .filter(|post| !published_only || post.is_public())
+        .filter_map(|post| {
+            let part = post
+                .series
+                .as_ref()
+                .filter(|part| &part.series == series)?
+                .part;
+            Some((part, post))
+        })
+        .sorted_by_key(|(part, _)| *part)
+        .map(|(_, post)| post)
+        .collect()
+}
+
+pub(super) fn translations(
+    blog_posts: impl IntoIterator<Item = BlogPost>,
+    translation_key: &str,
+    published_only: bool,
+) -> Vec<BlogPost> {
+    blog_posts
+        .into_iter()
+        .filter(|post| {
+            (!published_only || post.is_public())
+                && post.translation_key.as_deref() == Some(translation_key)
+        })
+        .sorted_by(|a, b| a.language.cmp(&b.language))
+        .collect()
+}
+
+pub(super) fn next_publication_date<'a>(
+    blog_posts: impl IntoIterator<Item = &'a BlogPost>,
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    blog_posts
+        .into_iter()
+        .filter(|post| post.status.is_released())
+        .filter_map(|post| post.publication_date)
+        .filter(|date| *date > after)
+        .min()
+}
+
+/// Matches posts containing every word of the query, without ranking or highlighting.
+pub(super) fn search(
+    blog_posts: impl IntoIterator<Item = BlogPost>,
+    search_query: &str,
+) -> Vec<SearchResult> {
+    let words = search_query
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect_vec();
+    if words.is_empty() {
+        return Vec::new();
+    }
+
+    blog_posts
+        .into_iter()
+        .filter(|post| {
+            let text = [&post.title]
+                .into_iter()
+                .chain(&post.description)
+                .chain([&post.plain_text])
+                .join("\n")
+                .to_lowercase();
+
+            words.iter().all(|word| text.contains(word))
+        })
+        .map(|blog_post| SearchResult {
+            snippet: vec![SnippetFragment {
+                text: blog_post.description.clone().unwrap_or_default(),
+                highlighted: false,
+            }],
+            blog_post,
+        })
+        .collect()
+}
+
+/// Mirrors the `blog_post_status_check` constraint, only released posts have a publication date.
+fn has_valid_publication_date(blog_post: &BlogPost) -> bool {
+    blog_post.status.is_released() == blog_post.publication_date.is_some()
+}
+
+/// Every change counts as an update, whatever `updated_at` the caller passed.
+fn changed_now(blog_post: &BlogPost) -> BlogPost {
+    BlogPost {
+        updated_at: Utc::now(),
+        ..blog_post.clone()
+    }
+}
+
+impl State {
+    fn check_translation_key(&self, blog_post: &BlogPost, ignored_url: &str) -> Result<()> {
+        let Some(translation_key) = &blog_post.translation_key else {
+            return Ok(());
+        };
+
+        let taken = self.blog_posts.values().any(|post| {
+            post.url != ignored_url
+                && post.translation_key.as_ref() == Some(translation_key)
+                && post.language == blog_post.language
+        });
+
+        if taken {
+            return Err(Error::InvalidInput);
+        }
+
+        Ok(())
+    }
+
+    fn insert_media_assets(&mut self, media_assets: &[MediaAsset]) {
+        for media_asset in media_assets {
+            self.media_assets
+                .entry(media_asset.hash.clone())
+                .or_insert_with(|| media_asset.clone());
+        }
+    }
+}
+
+#[async_trait]
+impl BlogStore for MemoryStore {
+    async fn migrate(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn get_blog_post(&self, url: &str, accessible_only: bool) -> Result<Option<BlogPost>> {
+        Ok(self
+            .state()
+            .blog_posts
+            .get(url)
+            .filter(|post| !accessible_only || post.is_accessible_or_public())
+            .cloned())
+    }
+
+    async fn get_blog_posts(
+        &self,
+        authors: Option<&[Author]>,
+        tags: Option<&[Tag]>,
+        languages: Option<&[Language]>,
+        published_only: bool,
+        selection: PostSelection,
+    ) -> Result<Vec<BlogPost>> {
+        let blog_posts = self.state().blog_posts.values().cloned().collect_vec();
+
+        Ok(filter_blog_posts(
+            blog_posts,
+            authors,
+            tags,
+            languages,
+            published_only,
+            selection,
+        ))
+    }
+
+    async fn get_blog_posts_page(
+        &self,
+        authors: Option<&[Author]>,
+        tags: Option<&[Tag]>,
+        languages: Option<&[Language]>,
+        published_only: bool,
+        selection: PostSelection,
+        boundary: Option<&PageBoundary>,
+        size: u32,
+    ) -> Result<Page> {
+        let blog_posts = self
+            .get_blog_posts(authors, tags, languages, published_only, selection)
+            .await?;
+
+        Ok(page_of(blog_posts, selection, boundary, size))
+    }
+
+    async fn get_tags(&self, published_only: bool) -> Result<Vec<Tag>> {
+        let blog_posts = self
+            .get_blog_posts(None, None, None, published_only, PostSelection::All)
+            .await?;
+
+        Ok(blog_posts
+            .into_iter()
+            .flat_map(|post| post.tags)
+            .sorted()
+            .dedup()
+            .collect())
+    }
+
+    async fn get_languages(&self, published_only: bool) -> Result<Vec<Language>> {
+        let blog_posts = self
+            .get_blog_posts(None, None, None, published_only, PostSelection::All)
+            .await?;
+
+        Ok(blog_posts
+            .into_iter()
+            .map(|post| post.language)
+            .sorted()
+            .dedup()
+            .collect())
+    }
+
+    async fn get_author(&self, author: &Author) -> Result<Option<AuthorProfile>> {
+        Ok(self.state().authors.get(author).cloned())
+    }
+
+    async fn get_authors(&self) -> Result<Vec<AuthorProfile>> {
+        Ok(self.state().authors.values().cloned().collect())
+    }
+
+    async fn search_blog_posts(
+        &self,
+        search_query: &str,
+        published_only: bool,
+    ) -> Result<Vec<SearchResult>> {
+        let blog_posts = self
+            .get_blog_posts(None, None, None, published_only, PostSelection::All)
+            .await?;
+
+        Ok(search(blog_posts, search_query))
+    }
+
+    async fn get_series_posts(
+        &self,
+        series: &Series,
+        published_only: bool,
+    ) -> Result<Vec<BlogPost>> {
+        let blog_posts = self.state().blog_posts.values().cloned().collect_vec();
+
+        Ok(series_posts(blog_posts, series, published_only))
+    }
+
+    async fn get_translations(
+        &self,
+        translation_key: &str,
+        published_only: bool,
+    ) -> Result<Vec<BlogPost>> {
+        let blog_posts = self.state().blog_posts.values().cloned().collect_vec();
+
+        Ok(translations(blog_posts, translation_key, published_only))
+    }
+
+    async fn get_url_redirect(&self, old_url: &str) -> Result<Option<String>> {
+        Ok(self.state().url_redirects.get(old_url).cloned())
+    }
+
+    async fn is_valid_preview_token(&self, url: &str, token: &str) -> Result<bool> {
+        Ok(self
+            .state()
+            .preview_tokens
+            .contains(&(url.to_string(), token.to_string())))
+    }
+
+    async fn get_media_asset(&self, hash: &str) -> Result<Option<MediaAsset>> {
+        Ok(self.state().media_assets.get(hash).cloned())
+    }
+
+    async fn get_next_publication_date(
+        &self,
+        after: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>> {
+        Ok(next_publication_date(
+            self.state().blog_posts.values(),
+            after,
+        ))
+    }
+
+    async fn insert_blog_post(
+        &self,
+        blog_post: &BlogPost,
+        media_assets: &[MediaAsset],
+    ) -> Result<()> {
+        let mut state = self.state();
+
+        if blog_post.authors.is_empty()
+            || !has_valid_publication_date(blog_post)
+            || state.blog_posts.contains_key(&blog_post.url)
+        {
+            return Err(Error::InvalidInput);
+        }
+        state.check_translation_key(blog_post, &blog_post.url)?;
+
+        state.insert_media_assets(media_assets);
+
+        // A live post takes precedence over redirects
+        state.url_redirects.remove(&blog_post.url);
+        state
+            .blog_posts
+            .insert(blog_post.url.clone(), changed_now(blog_post));
+
+        Ok(())
+    }
+
+    async fn update_blog_post(
+        &self,
+        original_url: Option<&str>,
+        blog_post: &BlogPost,
+        _changed_by: &Author,
+        media_assets: &[MediaAsset],
+    ) -> Result<()> {
+        let url = &blog_post.url;
+        let original_url = original_url.unwrap_or(url);
+        let mut state = self.state();
+
+        let renamed_onto_other_post = original_url != url && state.blog_posts.contains_key(url);
+        if blog_post.authors.is_empty()
+            || !has_valid_publication_date(blog_post)
+            || !state.blog_posts.contains_key(original_url)
+            || renamed_onto_other_post
+        {
+            return Err(Error::InvalidInput);
+        }
+        state.check_translation_key(blog_post, original_url)?;
+
+        state.insert_media_assets(media_assets);
+
+        state.blog_posts.remove(original_url);
+        state.blog_posts.insert(url.clone(), changed_now(blog_post));
+
+        // Redirect the old url to the new one, and keep earlier redirects and tokens pointing at
+        // the current url
+        if original_url != url {
+            for new_url in state.url_redirects.values_mut() {
+                if new_url == original_url {
+                    new_url.clone_from(url);
+                }
+            }
+            state.url_redirects.remove(url);
+            state
+                .url_redirects
+                .insert(original_url.to_string(), url.clone());
+
+            state.preview_tokens = state
+                .preview_tokens
+                .drain()
+                .map(|(post_url, token)| {
+                    if post_url == original_url {
+                        (url.clone(), token)
+                    } else {
+                        (post_url, token)
+                    }
+                })
+                .collect();
+        }
+
+        Ok(())
+    }
+
+    async fn update_rendered_blog_post(&self, blog_post: &BlogPost) -> Result<()> {
+        let mut state = self.state();
+        let stored = state
+            .blog_posts
+            .get_mut(&blog_post.url)
+            .ok_or(Error::InvalidInput)?;
+
+        *stored = BlogPost {
+            html: blog_post.html.clone(),
+            reading_time: blog_post.reading_time,
+            word_count: blog_post.word_count,
+            excerpt: blog_post.excerpt.clone(),
+            plain_text: blog_post.plain_text.clone(),
+            renderer_version: blog_post.renderer_version,
+            outline: blog_post.outline.clone(),
+            ..stored.clone()
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MemoryStore;
+    use crate::blog::{ignoring_update_times, BlogPost};
+    use crate::database::{Author, BlogStore, PageBoundary, PostSelection, PostStatus};
+
+    #[tokio::test]
+    async fn memory_store_test() -> super::Result<()> {
+        let store = MemoryStore::new();
+        let older = BlogPost::test_post("older", PostStatus::Published, Some(1));
+        let newer = BlogPost::test_post("newer", PostStatus::Published, Some(2));
+        for post in [&older, &newer] {
+            store.insert_blog_post(post, &[]).await?;
+        }
+
+        assert!(store.insert_blog_post(&older, &[]).await.is_err());
+        assert!(
+            store
+                .get_blog_post("older", false)
+                .await?
+                .unwrap()
+                .updated_at
+                > older.updated_at
+        );
+        let page = store
+            .get_blog_posts_page(None, None, None, true, PostSelection::All, None, 1)
+            .await?;
+        assert_eq!(
+            ignoring_update_times(page.blog_posts.clone()),
+            vec![newer.clone()]
+        );
+        let next = page.next.map(PageBoundary::After);
+        let page = store
+            .get_blog_posts_page(None, None, None, true, PostSelection::All, next.as_ref(), 1)
+            .await?;
+        assert_eq!(
+            ignoring_update_times(page.blog_posts.clone()),
+            vec![older.clone()]
+        );
+        assert_eq!(page.next, None);
+
+        // Renaming twice keeps the first url pointing at the current one
+        let author = Author("Quiet".to_string());
+        let renamed = BlogPost {
+            url: "renamed".to_string(),
+            ..older.clone()
+        };
+        store
+            .update_blog_post(Some("older"), &renamed, &author, &[])
+            .await?;
+        let renamed_again = BlogPost {
+            url: "renamed-again".to_string(),
+            ..older.clone()
+        };
+        store
+            .update_blog_post(Some("renamed"), &renamed_again, &author, &[])
+            .await?;
+
+        assert_eq!(
+            store.get_url_redirect("older").await?,
+            Some("renamed-again".to_string())
+        );
+        assert_eq!(
+            store.get_url_redirect("renamed").await?,
+            Some("renamed-again".to_string())
+        );
+
+        // Neither missing posts nor existing urls can be updated onto
+        assert!(store
+            .update_blog_post(Some("missing"), &older, &author, &[])
+            .await
+            .is_err());
+        assert!(store
+            .update_blog_post(Some("renamed-again"), &newer, &author, &[])
+            .await
+            .is_err());
+
+        Ok(())
+    }
+}
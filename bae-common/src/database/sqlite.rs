@@ -0,0 +1,1050 @@
+//! SQLite backend, so the blog can be previewed locally without a running Postgres.
+//!
+//! Only what is needed to upload and browse posts is supported: there are no revisions, and search
+//! only matches words without ranking them. Filtering and ordering happen in Rust, with the same
+//! helpers as [`super::MemoryStore`], which is fine for the size of a local database.
+
+use super::memory::{
+    filter_blog_posts, next_publication_date, page_of, search, series_posts, translations,
+};
+use super::{
+    series_part, Author, AuthorRecord, BlogPostRecord, BlogStore, Error, Language, Page,
+    PageBoundary, PostSelection, PostStatus, Result, Series, Tag,
+};
+use crate::blog::{AuthorLink, AuthorProfile, BlogPost, PreviewToken, SearchResult};
+use crate::markdown_render::Heading;
+use crate::media::MediaAsset;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::types::Json;
+use sqlx::{migrate, query, query_as, query_scalar, FromRow, Sqlite, SqlitePool, Transaction};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+const SELECT_BLOG_POSTS: &str = "SELECT url, title, description, \
+        (SELECT json_group_array(author) FROM (SELECT author FROM post_author \
+            WHERE post_author.url = blog_post.url ORDER BY position ASC)) AS authors, \
+        markdown, html, \
+        (SELECT json_group_array(tag) FROM (SELECT tag FROM tag \
+            WHERE tag.url = blog_post.url ORDER BY tag ASC)) AS tags, \
+        reading_time_minutes, status, publication_date, updated_at, series, series_part, \
+        language, translation_key, renderer_version, image_urls, pinned, featured, outline, \
+        table_of_contents, word_count, excerpt, plain_text \
+    FROM blog_post";
+
+const SELECT_AUTHORS: &str = "SELECT author, display_name, bio_markdown, bio_html, avatar_path, \
+        (SELECT json_group_array(label) FROM (SELECT label FROM author_link \
+            WHERE author_link.author = author.author ORDER BY position ASC)) AS link_labels, \
+        (SELECT json_group_array(url) FROM (SELECT url FROM author_link \
+            WHERE author_link.author = author.author ORDER BY position ASC)) AS link_urls \
+    FROM author";
+
+#[derive(FromRow)]
+struct SqliteBlogPostRecord {
+    url: String,
+    title: String,
+    description: String,
+    authors: Json<Vec<String>>,
+    markdown: Option<String>,
+    html: String,
+    tags: Json<Vec<String>>,
+    reading_time_minutes: i64,
+    status: PostStatus,
+    publication_date: Option<DateTime<Utc>>,
+    updated_at: DateTime<Utc>,
+    series: Option<String>,
+    series_part: Option<i32>,
+    language: String,
+    translation_key: Option<String>,
+    renderer_version: i32,
+    image_urls: Json<HashMap<String, String>>,
+    pinned: bool,
+    featured: bool,
+    outline: Json<Vec<Heading>>,
+    table_of_contents: bool,
+    word_count: i32,
+    excerpt: String,
+    plain_text: String,
+}
+
+impl TryFrom<SqliteBlogPostRecord> for BlogPost {
+    type Error = Error;
+
+    fn try_from(record: SqliteBlogPostRecord) -> Result<Self> {
+        BlogPost::try_from(BlogPostRecord {
+            url: record.url,
+            title: record.title,
+            description: record.description,
+            authors: record.authors.0,
+            markdown: record.markdown,
+            html: record.html,
+            tags: Some(record.tags.0),
+            reading_time_minutes: record.reading_time_minutes,
+            status: record.status,
+            publication_date: record.publication_date,
+            updated_at: record.updated_at,
+            series: record.series,
+            series_part: record.series_part,
+            language: record.language,
+            translation_key: record.translation_key,
+            renderer_version: record.renderer_version,
+            image_urls: record.image_urls,
+            pinned: record.pinned,
+            featured: record.featured,
+            outline: record.outline,
+            table_of_contents: record.table_of_contents,
+            word_count: record.word_count,
+            excerpt: record.excerpt,
+            plain_text: record.plain_text,
+        })
+    }
+}
+
+#[derive(FromRow)]
+struct SqliteAuthorRecord {
+    author: String,
+    display_name: Option<String>,
+    bio_markdown: Option<String>,
+    bio_html: Option<String>,
+    avatar_path: Option<String>,
+    link_labels: Json<Vec<String>>,
+    link_urls: Json<Vec<String>>,
+}
+
+impl TryFrom<SqliteAuthorRecord> for AuthorProfile {
+    type Error = Error;
+
+    fn try_from(record: SqliteAuthorRecord) -> Result<Self> {
+        AuthorProfile::try_from(AuthorRecord {
+            author: Author(record.author),
+            display_name: record.display_name,
+            bio_markdown: record.bio_markdown,
+            bio_html: record.bio_html,
+            avatar_path: record.avatar_path,
+            link_labels: Some(record.link_labels.0),
+            link_urls: Some(record.link_urls.0),
+        })
+    }
+}
+
+/// The database file is created if it does not exist yet.
+pub async fn connect(database_url: &str) -> Result<SqlitePool> {
+    let options = SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+
+    Ok(SqlitePool::connect_with(options).await?)
+}
+
+pub async fn migrate(pool: &SqlitePool) -> Result<()> {
+    migrate!("./migrations-sqlite")
+        .run(pool)
+        .await
+        .map_err(sqlx::Error::from)
+        .map_err(Error::from)
+}
+
+pub async fn get_blog_post(
+    url: &str,
+    accessible_only: bool,
+    pool: &SqlitePool,
+) -> Result<Option<BlogPost>> {
+    let blog_post =
+        query_as::<_, SqliteBlogPostRecord>(&format!("{SELECT_BLOG_POSTS} WHERE url = ?"))
+            .bind(url)
+            .fetch_optional(pool)
+            .await?
+            .map(BlogPost::try_from)
+            .transpose()?;
+
+    Ok(blog_post.filter(|post| !accessible_only || post.is_accessible_or_public()))
+}
+
+pub async fn get_blog_posts(
+    authors: Option<&[Author]>,
+    tags: Option<&[Tag]>,
+    languages: Option<&[Language]>,
+    published_only: bool,
+    selection: PostSelection,
+    pool: &SqlitePool,
+) -> Result<Vec<BlogPost>> {
+    let blog_posts: Vec<_> = query_as::<_, SqliteBlogPostRecord>(SELECT_BLOG_POSTS)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(BlogPost::try_from)
+        .try_collect()?;
+
+    Ok(filter_blog_posts(
+        blog_posts,
+        authors,
+        tags,
+        languages,
+        published_only,
+        selection,
+    ))
+}
+
+/// Like [`get_blog_posts`], but returns at most `size` posts starting at `boundary`.
+#[allow(clippy::too_many_arguments)]
+pub async fn get_blog_posts_page(
+    authors: Option<&[Author]>,
+    tags: Option<&[Tag]>,
+    languages: Option<&[Language]>,
+    published_only: bool,
+    selection: PostSelection,
+    boundary: Option<&PageBoundary>,
+    size: u32,
+    pool: &SqlitePool,
+) -> Result<Page> {
+    let blog_posts =
+        get_blog_posts(authors, tags, languages, published_only, selection, pool).await?;
+
+    Ok(page_of(blog_posts, selection, boundary, size))
+}
+
+pub async fn get_tags(published_only: bool, pool: &SqlitePool) -> Result<Vec<Tag>> {
+    let blog_posts =
+        get_blog_posts(None, None, None, published_only, PostSelection::All, pool).await?;
+
+    Ok(blog_posts
+        .into_iter()
+        .flat_map(|post| post.tags)
+        .sorted()
+        .dedup()
+        .collect())
+}
+
+pub async fn get_languages(published_only: bool, pool: &SqlitePool) -> Result<Vec<Language>> {
+    let blog_posts =
+        get_blog_posts(None, None, None, published_only, PostSelection::All, pool).await?;
+
+    Ok(blog_posts
+        .into_iter()
+        .map(|post| post.language)
+        .sorted()
+        .dedup()
+        .collect())
+}
+
+pub async fn get_author(author: &Author, pool: &SqlitePool) -> Result<Option<AuthorProfile>> {
+    query_as::<_, SqliteAuthorRecord>(&format!("{SELECT_AUTHORS} WHERE author = ?"))
+        .bind(&author.0)
+        .fetch_optional(pool)
+        .await?
+        .map(AuthorProfile::try_from)
+        .transpose()
+}
+
+pub async fn get_authors(pool: &SqlitePool) -> Result<Vec<AuthorProfile>> {
+    let author_profiles =
+        query_as::<_, SqliteAuthorRecord>(&format!("{SELECT_AUTHORS} ORDER BY author ASC"))
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(AuthorProfile::try_from)
+            .try_collect()?;
+
+    Ok(author_profiles)
+}
+
+/// Matches posts containing every word of the query, like [`super::MemoryStore`] does.
+pub async fn search_blog_posts(
+    search_query: &str,
+    published_only: bool,
+    pool: &SqlitePool,
+) -> Result<Vec<SearchResult>> {
+    let blog_posts =
+        get_blog_posts(None, None, None, published_only, PostSelection::All, pool).await?;
+
+    Ok(search(blog_posts, search_query))
+}
+
+/// All posts of a series, ordered by their part number.
+pub async fn get_series_posts(
+    series: &Series,
+    published_only: bool,
+    pool: &SqlitePool,
+) -> Result<Vec<BlogPost>> {
+    let blog_posts = get_blog_posts(None, None, None, false, PostSelection::All, pool).await?;
+
+    Ok(series_posts(blog_posts, series, published_only))
+}
+
+/// All translations of an article, including the post itself, ordered by language.
+pub async fn get_translations(
+    translation_key: &str,
+    published_only: bool,
+    pool: &SqlitePool,
+) -> Result<Vec<BlogPost>> {
+    let blog_posts = get_blog_posts(None, None, None, false, PostSelection::All, pool).await?;
+
+    Ok(translations(blog_posts, translation_key, published_only))
+}
+
+pub async fn get_url_redirect(old_url: &str, pool: &SqlitePool) -> Result<Option<String>> {
+    query_scalar("SELECT new_url FROM url_redirect WHERE old_url = ?")
+        .bind(old_url)
+        .fetch_optional(pool)
+        .await
+        .map_err(Error::from)
+}
+
+pub async fn is_valid_preview_token(url: &str, token: &str, pool: &SqlitePool) -> Result<bool> {
+    query_scalar("SELECT EXISTS(SELECT * FROM preview_token WHERE post_url = ? AND token = ?)")
+        .bind(url)
+        .bind(token)
+        .fetch_one(pool)
+        .await
+        .map_err(Error::from)
+}
+
+pub async fn get_next_publication_date(
+    after: DateTime<Utc>,
+    pool: &SqlitePool,
+) -> Result<Option<DateTime<Utc>>> {
+    let blog_posts = get_blog_posts(None, None, None, false, PostSelection::All, pool).await?;
+
+    Ok(next_publication_date(&blog_posts, after))
+}
+
+pub async fn get_media_asset(hash: &str, pool: &SqlitePool) -> Result<Option<MediaAsset>> {
+    let media_asset = query_as::<_, (String, String, Vec<u8>)>(
+        "SELECT hash, content_type, data FROM media_asset WHERE hash = ?",
+    )
+    .bind(hash)
+    .fetch_optional(pool)
+    .await?
+    .map(|(hash, content_type, data)| MediaAsset {
+        hash,
+        content_type,
+        data,
+    });
+
+    Ok(media_asset)
+}
+
+/// Stores `media_asset` unless an asset with the same hash already exists.
+pub async fn insert_media_asset(
+    MediaAsset {
+        hash,
+        content_type,
+        data,
+    }: &MediaAsset,
+    transaction: &mut Transaction<'_, Sqlite>,
+) -> Result<()> {
+    query(
+        "INSERT INTO media_asset (hash, content_type, data) VALUES (?, ?, ?) \
+        ON CONFLICT DO NOTHING",
+    )
+    .bind(hash)
+    .bind(content_type)
+    .bind(data)
+    .execute(&mut **transaction)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn insert_blog_post(
+    blog_post: &BlogPost,
+    transaction: &mut Transaction<'_, Sqlite>,
+) -> Result<()> {
+    if blog_post.authors.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+
+    // A live post takes precedence over redirects
+    query("DELETE FROM url_redirect WHERE old_url = ?")
+        .bind(&blog_post.url)
+        .execute(&mut **transaction)
+        .await?;
+
+    query(
+        "INSERT INTO blog_post \
+            (title, description, markdown, html, reading_time_minutes, status, \
+                publication_date, updated_at, series, series_part, language, translation_key, \
+                renderer_version, image_urls, pinned, featured, outline, table_of_contents, \
+                word_count, excerpt, plain_text, url) \
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind_blog_post(blog_post)?
+    .execute(&mut **transaction)
+    .await?;
+
+    insert_tags_and_authors(blog_post, transaction).await
+}
+
+/// Unlike the Postgres backend, this does not record a revision.
+pub async fn update_blog_post(
+    original_url: Option<&str>,
+    blog_post: &BlogPost,
+    transaction: &mut Transaction<'_, Sqlite>,
+) -> Result<()> {
+    let url = &blog_post.url;
+    let original_url = original_url.unwrap_or(url);
+
+    if blog_post.authors.is_empty() {
+        return Err(Error::InvalidInput);
+    }
+
+    // Tags and authors are inserted again below
+    for statement in [
+        "DELETE FROM tag WHERE url = ?",
+        "DELETE FROM post_author WHERE url = ?",
+    ] {
+        query(statement)
+            .bind(original_url)
+            .execute(&mut **transaction)
+            .await?;
+    }
+
+    let updated = query(
+        "UPDATE blog_post \
+        SET title=?, description=?, markdown=?, html=?, reading_time_minutes=?, status=?, \
+            publication_date=?, updated_at=?, series=?, series_part=?, language=?, \
+            translation_key=?, renderer_version=?, image_urls=?, pinned=?, featured=?, outline=?, \
+            table_of_contents=?, word_count=?, excerpt=?, plain_text=?, url=? \
+        WHERE url = ?",
+    )
+    .bind_blog_post(blog_post)?
+    .bind(original_url)
+    .execute(&mut **transaction)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    // Redirect the old url to the new one
+    if original_url != url {
+        query("DELETE FROM url_redirect WHERE old_url = ?")
+            .bind(url)
+            .execute(&mut **transaction)
+            .await?;
+
+        query("INSERT INTO url_redirect (old_url, new_url) VALUES (?, ?)")
+            .bind(original_url)
+            .bind(url)
+            .execute(&mut **transaction)
+            .await?;
+    }
+
+    insert_tags_and_authors(blog_post, transaction).await
+}
+
+/// Writes the fields the renderer produces, see [`BlogStore::update_rendered_blog_post`].
+pub async fn update_rendered_blog_post(
+    BlogPost {
+        url,
+        html,
+        reading_time,
+        word_count,
+        excerpt,
+        plain_text,
+        renderer_version,
+        outline,
+        ..
+    }: &BlogPost,
+    pool: &SqlitePool,
+) -> Result<()> {
+    let updated = query(
+        "UPDATE blog_post \
+        SET html=?, reading_time_minutes=?, renderer_version=?, outline=?, word_count=?, \
+            excerpt=?, plain_text=? \
+        WHERE url = ?",
+    )
+    .bind(html)
+    .bind(reading_time.num_minutes())
+    .bind(i32::try_from(*renderer_version).map_err(|_| Error::InvalidInput)?)
+    .bind(Json(outline))
+    .bind(word_count)
+    .bind(excerpt)
+    .bind(plain_text)
+    .bind(url)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    Ok(())
+}
+
+pub async fn insert_author(
+    author_profile: &AuthorProfile,
+    transaction: &mut Transaction<'_, Sqlite>,
+) -> Result<()> {
+    query("INSERT INTO author (author) VALUES (?)")
+        .bind(&author_profile.author.0)
+        .execute(&mut **transaction)
+        .await?;
+
+    update_author(author_profile, transaction).await
+}
+
+pub async fn update_author(
+    AuthorProfile {
+        author,
+        display_name,
+        bio_markdown,
+        bio_html,
+        avatar_path,
+        links,
+    }: &AuthorProfile,
+    transaction: &mut Transaction<'_, Sqlite>,
+) -> Result<()> {
+    let updated = query(
+        "UPDATE author \
+        SET display_name=?, bio_markdown=?, bio_html=?, avatar_path=? \
+        WHERE author = ?",
+    )
+    .bind(display_name)
+    .bind(bio_markdown)
+    .bind(bio_html)
+    .bind(avatar_path)
+    .bind(&author.0)
+    .execute(&mut **transaction)
+    .await?
+    .rows_affected();
+
+    if updated == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    // Replace links
+    query("DELETE FROM author_link WHERE author = ?")
+        .bind(&author.0)
+        .execute(&mut **transaction)
+        .await?;
+
+    for (position, AuthorLink { label, url }) in links.iter().enumerate() {
+        query("INSERT INTO author_link (author, position, label, url) VALUES (?, ?, ?, ?)")
+            .bind(&author.0)
+            .bind(i64::try_from(position).map_err(|_| Error::InvalidInput)?)
+            .bind(label)
+            .bind(url)
+            .execute(&mut **transaction)
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn create_preview_token(
+    url: &str,
+    created_by: &Author,
+    transaction: &mut Transaction<'_, Sqlite>,
+) -> Result<PreviewToken> {
+    let created_at = Utc::now();
+
+    // randomblob is seeded from the operating system's random number generator
+    let token = query_scalar(
+        "INSERT INTO preview_token (token, post_url, created_at, created_by) \
+        VALUES (lower(hex(randomblob(16))), ?, ?, ?) \
+        RETURNING token",
+    )
+    .bind(url)
+    .bind(created_at)
+    .bind(&created_by.0)
+    .fetch_one(&mut **transaction)
+    .await?;
+
+    Ok(PreviewToken {
+        token,
+        post_url: url.to_string(),
+        created_at,
+        created_by: created_by.clone(),
+    })
+}
+
+async fn insert_tags_and_authors(
+    BlogPost {
+        url, tags, authors, ..
+    }: &BlogPost,
+    transaction: &mut Transaction<'_, Sqlite>,
+) -> Result<()> {
+    for Tag(tag) in tags {
+        query("INSERT INTO tag (tag, url) VALUES (?, ?)")
+            .bind(tag)
+            .bind(url)
+            .execute(&mut **transaction)
+            .await?;
+    }
+
+    for (position, Author(author)) in authors.iter().enumerate() {
+        query("INSERT INTO post_author (url, author, position) VALUES (?, ?, ?)")
+            .bind(url)
+            .bind(author)
+            .bind(i64::try_from(position).map_err(|_| Error::InvalidInput)?)
+            .execute(&mut **transaction)
+            .await?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl BlogStore for SqlitePool {
+    async fn migrate(&self) -> Result<()> {
+        migrate(self).await
+    }
+
+    async fn get_blog_post(&self, url: &str, accessible_only: bool) -> Result<Option<BlogPost>> {
+        get_blog_post(url, accessible_only, self).await
+    }
+
+    async fn get_blog_posts(
+        &self,
+        authors: Option<&[Author]>,
+        tags: Option<&[Tag]>,
+        languages: Option<&[Language]>,
+        published_only: bool,
+        selection: PostSelection,
+    ) -> Result<Vec<BlogPost>> {
+        get_blog_posts(authors, tags, languages, published_only, selection, self).await
+    }
+
+    async fn get_blog_posts_page(
+        &self,
+        authors: Option<&[Author]>,
+        tags: Option<&[Tag]>,
+        languages: Option<&[Language]>,
+        published_only: bool,
+        selection: PostSelection,
+        boundary: Option<&PageBoundary>,
+        size: u32,
+    ) -> Result<Page> {
+        get_blog_posts_page(
+            authors,
+            tags,
+            languages,
+            published_only,
+            selection,
+            boundary,
+            size,
+            self,
+        )
+        .await
+    }
+
+    async fn get_tags(&self, published_only: bool) -> Result<Vec<Tag>> {
+        get_tags(published_only, self).await
+    }
+
+    async fn get_languages(&self, published_only: bool) -> Result<Vec<Language>> {
+        get_languages(published_only, self).await
+    }
+
+    async fn get_author(&self, author: &Author) -> Result<Option<AuthorProfile>> {
+        get_author(author, self).await
+    }
+
+    async fn get_authors(&self) -> Result<Vec<AuthorProfile>> {
+        get_authors(self).await
+    }
+
+    async fn search_blog_posts(
+        &self,
+        search_query: &str,
+        published_only: bool,
+    ) -> Result<Vec<SearchResult>> {
+        search_blog_posts(search_query, published_only, self).await
+    }
+
+    async fn get_series_posts(
+        &self,
+        series: &Series,
+        published_only: bool,
+    ) -> Result<Vec<BlogPost>> {
+        get_series_posts(series, published_only, self).await
+    }
+
+    async fn get_translations(
+        &self,
+        translation_key: &str,
+        published_only: bool,
+    ) -> Result<Vec<BlogPost>> {
+        get_translations(translation_key, published_only, self).await
+    }
+
+    async fn get_url_redirect(&self, old_url: &str) -> Result<Option<String>> {
+        get_url_redirect(old_url, self).await
+    }
+
+    async fn is_valid_preview_token(&self, url: &str, token: &str) -> Result<bool> {
+        is_valid_preview_token(url, token, self).await
+    }
+
+    async fn get_media_asset(&self, hash: &str) -> Result<Option<MediaAsset>> {
+        get_media_asset(hash, self).await
+    }
+
+    async fn get_next_publication_date(
+        &self,
+        after: DateTime<Utc>,
+    ) -> Result<Option<DateTime<Utc>>> {
+        get_next_publication_date(after, self).await
+    }
+
+    async fn insert_blog_post(
+        &self,
+        blog_post: &BlogPost,
+        media_assets: &[MediaAsset],
+    ) -> Result<()> {
+        let mut transaction = self.begin().await?;
+        for media_asset in media_assets {
+            insert_media_asset(media_asset, &mut transaction).await?;
+        }
+        insert_blog_post(blog_post, &mut transaction).await?;
+
+        Ok(transaction.commit().await?)
+    }
+
+    async fn update_blog_post(
+        &self,
+        original_url: Option<&str>,
+        blog_post: &BlogPost,
+        _changed_by: &Author,
+        media_assets: &[MediaAsset],
+    ) -> Result<()> {
+        let mut transaction = self.begin().await?;
+        for media_asset in media_assets {
+            insert_media_asset(media_asset, &mut transaction).await?;
+        }
+        update_blog_post(original_url, blog_post, &mut transaction).await?;
+
+        Ok(transaction.commit().await?)
+    }
+
+    async fn update_rendered_blog_post(&self, blog_post: &BlogPost) -> Result<()> {
+        update_rendered_blog_post(blog_post, self).await
+    }
+}
+
+type SqliteQuery<'q> = sqlx::query::Query<'q, Sqlite, sqlx::sqlite::SqliteArguments<'q>>;
+
+trait BindBlogPost<'q>: Sized {
+    /// Binds the columns of `blog_post` in the order used by the insert and update statements.
+    fn bind_blog_post(self, blog_post: &'q BlogPost) -> Result<Self>;
+}
+
+impl<'q> BindBlogPost<'q> for SqliteQuery<'q> {
+    fn bind_blog_post(self, blog_post: &'q BlogPost) -> Result<Self> {
+        Ok(self
+            .bind(&blog_post.title)
+            .bind(blog_post.description.as_deref().unwrap_or_default())
+            .bind(&blog_post.markdown)
+            .bind(&blog_post.html)
+            .bind(blog_post.reading_time.num_minutes())
+            .bind(blog_post.status)
+            .bind(blog_post.publication_date)
+            // Every change counts as an update
+            .bind(Utc::now())
+            .bind(blog_post.series.as_ref().map(|series| &series.series.0))
+            .bind(series_part(blog_post.series.as_ref())?)
+            .bind(&blog_post.language.0)
+            .bind(&blog_post.translation_key)
+            .bind(i32::try_from(blog_post.renderer_version).map_err(|_| Error::InvalidInput)?)
+            .bind(Json(&blog_post.image_urls))
+            .bind(blog_post.pinned)
+            .bind(blog_post.featured)
+            .bind(Json(&blog_post.outline))
+            .bind(blog_post.table_of_contents)
+            .bind(blog_post.word_count)
+            .bind(&blog_post.excerpt)
+            .bind(&blog_post.plain_text)
+            .bind(&blog_post.url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::blog::{ignoring_update_times, AuthorLink, AuthorProfile, BlogPost, SearchResult};
+    use crate::database::{Author, PageBoundary, PostSelection, PostStatus, Tag};
+    use crate::markdown_render::Heading;
+    use sqlx::SqlitePool;
+    use std::collections::HashMap;
+
+    /// Fills every column, so the round trip through the database is checked for all of them.
+    fn blog_post(url: &str, status: PostStatus, publication_date: Option<i64>) -> BlogPost {
+        BlogPost {
+            authors: vec![Author("Quiet".to_string()), Author("gpluscb".to_string())],
+            tags: vec![Tag(url.to_string()), Tag("post".to_string())],
+            renderer_version: 1,
+            image_urls: HashMap::from([("image.png".to_string(), "/media/hash.png".to_string())]),
+            pinned: url == "older",
+            featured: url == "older",
+            outline: vec![Heading {
+                level: 1,
+                text: url.to_string(),
+                id: None,
+            }],
+            table_of_contents: true,
+            ..BlogPost::test_post(url, status, publication_date)
+        }
+    }
+
+    #[sqlx::test(migrations = "./migrations-sqlite")]
+    async fn blog_posts_test(pool: SqlitePool) -> super::Result<()> {
+        let older = blog_post("older", PostStatus::Published, Some(1));
+        let newer = blog_post("newer", PostStatus::Published, Some(2));
+        let draft = blog_post("draft", PostStatus::Draft, None);
+
+        let mut transaction = pool.begin().await?;
+        for post in [&older, &newer, &draft] {
+            super::insert_blog_post(post, &mut transaction).await?;
+        }
+        transaction.commit().await?;
+
+        assert_eq!(
+            super::get_blog_post("older", true, &pool)
+                .await?
+                .map(BlogPost::ignoring_update_time),
+            Some(older.clone())
+        );
+        assert_eq!(super::get_blog_post("draft", true, &pool).await?, None);
+        assert_eq!(
+            ignoring_update_times(
+                super::get_blog_posts(None, None, None, false, PostSelection::All, &pool).await?
+            ),
+            vec![newer.clone(), older.clone(), draft.clone()],
+        );
+        assert_eq!(
+            ignoring_update_times(
+                super::get_blog_posts(
+                    None,
+                    Some(&[Tag("older".to_string())]),
+                    None,
+                    true,
+                    PostSelection::All,
+                    &pool
+                )
+                .await?
+            ),
+            vec![older.clone()],
+        );
+        assert_eq!(
+            ignoring_update_times(
+                super::get_blog_posts(None, None, None, false, PostSelection::PinnedFirst, &pool)
+                    .await?
+            ),
+            vec![older.clone(), newer.clone(), draft.clone()],
+        );
+        assert_eq!(
+            ignoring_update_times(
+                super::get_blog_posts(None, None, None, false, PostSelection::FeaturedOnly, &pool)
+                    .await?
+            ),
+            vec![older.clone()],
+        );
+        assert_eq!(
+            super::get_tags(true, &pool).await?,
+            vec![
+                Tag("newer".to_string()),
+                Tag("older".to_string()),
+                Tag("post".to_string()),
+            ],
+        );
+
+        // Paging forwards and back again
+        let first =
+            super::get_blog_posts_page(None, None, None, false, PostSelection::All, None, 2, &pool)
+                .await?;
+        assert_eq!(
+            ignoring_update_times(first.blog_posts.clone()),
+            vec![newer.clone(), older.clone()]
+        );
+        let next = first.next.map(PageBoundary::After);
+        let second = super::get_blog_posts_page(
+            None,
+            None,
+            None,
+            false,
+            PostSelection::All,
+            next.as_ref(),
+            2,
+            &pool,
+        )
+        .await?;
+        assert_eq!(
+            ignoring_update_times(second.blog_posts.clone()),
+            vec![draft.clone()]
+        );
+        assert_eq!(second.next, None);
+        let previous = second.previous.map(PageBoundary::Before);
+        let back = super::get_blog_posts_page(
+            None,
+            None,
+            None,
+            false,
+            PostSelection::All,
+            previous.as_ref(),
+            2,
+            &pool,
+        )
+        .await?;
+        assert_eq!(back.blog_posts, first.blog_posts);
+        assert_eq!(back.previous, None);
+
+        // Renaming a post leaves a redirect and moves its tags and authors
+        let renamed = BlogPost {
+            url: "renamed".to_string(),
+            tags: vec![Tag("renamed".to_string())],
+            authors: vec![Author("gpluscb".to_string())],
+            ..older.clone()
+        };
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(Some("older"), &renamed, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(super::get_blog_post("older", false, &pool).await?, None);
+        assert_eq!(
+            super::get_blog_post("renamed", false, &pool)
+                .await?
+                .map(BlogPost::ignoring_update_time),
+            Some(renamed)
+        );
+        assert_eq!(
+            super::get_url_redirect("older", &pool).await?,
+            Some("renamed".to_string())
+        );
+
+        // Rerendering keeps the update time
+        let stored = super::get_blog_post("newer", false, &pool)
+            .await?
+            .expect("newer was inserted");
+        let rerendered = BlogPost {
+            html: "<p>Rerendered</p>".to_string(),
+            renderer_version: 2,
+            ..stored
+        };
+        super::update_rendered_blog_post(&rerendered, &pool).await?;
+        assert_eq!(
+            super::get_blog_post("newer", false, &pool).await?,
+            Some(rerendered)
+        );
+
+        // Updating a post that does not exist fails
+        let mut transaction = pool.begin().await?;
+        assert!(
+            super::update_blog_post(Some("missing"), &draft, &mut transaction)
+                .await
+                .is_err()
+        );
+
+        // Only released posts have a publication date
+        let dated_draft = blog_post("dated_draft", PostStatus::Draft, Some(1));
+        assert!(super::insert_blog_post(&dated_draft, &mut transaction)
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations-sqlite")]
+    async fn authors_and_preview_tokens_test(pool: SqlitePool) -> super::Result<()> {
+        let draft = blog_post("draft", PostStatus::Draft, None);
+        let author_profile = AuthorProfile {
+            author: Author("Quiet".to_string()),
+            display_name: Some("Quiet Person".to_string()),
+            bio_markdown: Some("Hi".to_string()),
+            bio_html: Some("<p>Hi</p>".to_string()),
+            avatar_path: None,
+            links: vec![
+                AuthorLink {
+                    label: "Website".to_string(),
+                    url: "https://example.com".to_string(),
+                },
+                AuthorLink {
+                    label: "Mastodon".to_string(),
+                    url: "https://example.social/@quiet".to_string(),
+                },
+            ],
+        };
+
+        let mut transaction = pool.begin().await?;
+        super::insert_blog_post(&draft, &mut transaction).await?;
+        super::insert_author(&author_profile, &mut transaction).await?;
+        let preview_token =
+            super::create_preview_token("draft", &author_profile.author, &mut transaction).await?;
+        transaction.commit().await?;
+
+        assert_eq!(
+            super::get_author(&author_profile.author, &pool).await?,
+            Some(author_profile.clone())
+        );
+        assert_eq!(
+            super::get_author(&Author("gpluscb".to_string()), &pool).await?,
+            None
+        );
+        assert_eq!(
+            super::get_authors(&pool).await?,
+            vec![author_profile.clone()]
+        );
+
+        assert_eq!(preview_token.token.len(), 32);
+        assert!(super::is_valid_preview_token("draft", &preview_token.token, &pool).await?);
+        assert!(!super::is_valid_preview_token("draft", "guessed", &pool).await?);
+
+        // Tokens follow the post when it is renamed
+        let renamed = BlogPost {
+            url: "renamed".to_string(),
+            ..draft
+        };
+        let mut transaction = pool.begin().await?;
+        super::update_blog_post(Some("draft"), &renamed, &mut transaction).await?;
+        transaction.commit().await?;
+        assert!(super::is_valid_preview_token("renamed", &preview_token.token, &pool).await?);
+
+        // Links are replaced on update
+        let updated_profile = AuthorProfile {
+            links: vec![],
+            ..author_profile
+        };
+        let mut transaction = pool.begin().await?;
+        super::update_author(&updated_profile, &mut transaction).await?;
+        transaction.commit().await?;
+        assert_eq!(
+            super::get_author(&updated_profile.author, &pool).await?,
+            Some(updated_profile)
+        );
+
+        Ok(())
+    }
+
+    #[sqlx::test(migrations = "./migrations-sqlite")]
+    async fn search_test(pool: SqlitePool) -> super::Result<()> {
+        let public = blog_post("public", PostStatus::Published, Some(1));
+        let draft = blog_post("draft", PostStatus::Draft, None);
+
+        let mut transaction = pool.begin().await?;
+        for post in [&public, &draft] {
+            super::insert_blog_post(post, &mut transaction).await?;
+        }
+        transaction.commit().await?;
+
+        let urls = |results: Vec<SearchResult>| {
+            results
+                .into_iter()
+                .map(|result| result.blog_post.url)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            urls(super::search_blog_posts("CONTENTS of", true, &pool).await?),
+            vec!["public"]
+        );
+        assert_eq!(
+            urls(super::search_blog_posts("contents", false, &pool).await?),
+            vec!["public", "draft"]
+        );
+        assert!(super::search_blog_posts("contents missing", false, &pool)
+            .await?
+            .is_empty());
+
+        Ok(())
+    }
+}
@@ -212,12 +212,16 @@ pub fn write_html_highlight_start<W: Write + ?Sized, G: CssClassNameGenerator>(
     write!(out, "<{elem}")?;
 
     let additional_classes = additional_attributes.get("class");
-    let mut classes_with_spaces = class_name_generator
-        .class_for_highlight(HIGHLIGHT_NAMES[highlight_idx], highlight_idx)
-        .into_iter()
-        .chain(additional_classes.and_then(|string| Some(Cow::Borrowed(string.as_ref()?.deref()))))
-        .intersperse(Cow::Borrowed(" "))
-        .peekable();
+    let mut classes_with_spaces = Itertools::intersperse(
+        class_name_generator
+            .class_for_highlight(HIGHLIGHT_NAMES[highlight_idx], highlight_idx)
+            .into_iter()
+            .chain(
+                additional_classes.and_then(|string| Some(Cow::Borrowed(string.as_ref()?.deref()))),
+            ),
+        Cow::Borrowed(" "),
+    )
+    .peekable();
 
     if classes_with_spaces.peek().is_some() {
         out.write_all(b" class=\"")?;
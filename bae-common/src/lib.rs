@@ -2,3 +2,4 @@ pub mod blog;
 pub mod database;
 pub mod highlighting;
 pub mod markdown_render;
+pub mod media;
@@ -1,15 +1,23 @@
 use crate::highlighting::{write_html_highlight_end, write_html_highlight_start, HIGHLIGHT_NAMES};
+use chrono::Duration;
 use pulldown_cmark::{
     CodeBlockKind, CowStr, Event, MetadataBlockKind, Options, Parser, Tag, TagEnd,
 };
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use thiserror::Error;
-use tracing::error;
 use tree_sitter::QueryError;
 use tree_sitter_highlight::{HighlightConfiguration, HighlightEvent, Highlighter};
 
+/// Version of the html [`render_md_to_html`] produces with the standard configuration.
+///
+/// Bump this whenever a change to rendering, like a new language in
+/// [`CodeBlockHighlighter::standard_config`] or different class names, should apply to posts that
+/// were already uploaded. The CLI's `rerender` command then renders those posts again.
+pub const RENDERER_VERSION: u32 = 7;
+
 fn escape_byte(byte: u8) -> Option<&'static str> {
     match byte {
         b'>' => Some("&gt;"),
@@ -34,39 +42,58 @@ fn escape(string: &mut String) {
 }
 
 pub trait CssClassNameGenerator {
-    fn class_for_highlight(&self, highlight_name: &str, highlight_idx: usize) -> Option<Cow<str>>;
-    fn class_for_image(&self) -> Option<Cow<str>>;
+    fn class_for_highlight(
+        &self,
+        highlight_name: &str,
+        highlight_idx: usize,
+    ) -> Option<Cow<'_, str>>;
+    fn class_for_image(&self) -> Option<Cow<'_, str>>;
+    fn class_for_permalink(&self) -> Option<Cow<'_, str>>;
 }
 
 pub struct FunctionCssClassNameGenerator<F> {
     highlight_class_function: F,
     image_class: Option<String>,
+    permalink_class: Option<String>,
 }
 
 impl<F> CssClassNameGenerator for FunctionCssClassNameGenerator<F>
 where
     F: Fn(&str, usize) -> Option<String>,
 {
-    fn class_for_highlight(&self, highlight_name: &str, highlight_idx: usize) -> Option<Cow<str>> {
+    fn class_for_highlight(
+        &self,
+        highlight_name: &str,
+        highlight_idx: usize,
+    ) -> Option<Cow<'_, str>> {
         Some(Cow::Owned((self.highlight_class_function)(
             highlight_name,
             highlight_idx,
         )?))
     }
 
-    fn class_for_image(&self) -> Option<Cow<str>> {
+    fn class_for_image(&self) -> Option<Cow<'_, str>> {
         self.image_class.as_deref().map(Cow::Borrowed)
     }
+
+    fn class_for_permalink(&self) -> Option<Cow<'_, str>> {
+        self.permalink_class.as_deref().map(Cow::Borrowed)
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct StandardClassNameGenerator {
     pub highlight_class_prefix: String,
     pub image_class: String,
+    pub permalink_class: String,
 }
 
 impl CssClassNameGenerator for StandardClassNameGenerator {
-    fn class_for_highlight(&self, highlight_name: &str, _highlight_idx: usize) -> Option<Cow<str>> {
+    fn class_for_highlight(
+        &self,
+        highlight_name: &str,
+        _highlight_idx: usize,
+    ) -> Option<Cow<'_, str>> {
         let mut output = self.highlight_class_prefix.clone();
         if !output.is_empty() {
             output.push('-');
@@ -75,9 +102,13 @@ impl CssClassNameGenerator for StandardClassNameGenerator {
         Some(Cow::Owned(output))
     }
 
-    fn class_for_image(&self) -> Option<Cow<str>> {
+    fn class_for_image(&self) -> Option<Cow<'_, str>> {
         Some(Cow::Borrowed(&self.image_class))
     }
+
+    fn class_for_permalink(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(&self.permalink_class))
+    }
 }
 
 impl StandardClassNameGenerator {
@@ -85,6 +116,7 @@ impl StandardClassNameGenerator {
         StandardClassNameGenerator {
             highlight_class_prefix: "highlight".to_string(),
             image_class: "blog-image".to_string(),
+            permalink_class: "heading-permalink".to_string(),
         }
     }
 }
@@ -219,7 +251,7 @@ impl<G: CssClassNameGenerator> CodeBlockHighlighter<G> {
         for highlight in highlights {
             match highlight {
                 HighlightEvent::Source { start, end } => {
-                    output.write_all(code[start..end].as_bytes())?;
+                    output.write_all(&code.as_bytes()[start..end])?;
                 }
                 HighlightEvent::HighlightStart(highlight) => {
                     write_html_highlight_start(
@@ -255,9 +287,51 @@ impl<G: CssClassNameGenerator> CodeBlockHighlighter<G> {
     }
 }
 
+/// Whether an image url points to a file next to the markdown, rather than to a site path or
+/// another origin.
+pub fn is_local_path(url: &str) -> bool {
+    !url.is_empty() && !url.starts_with(['/', '#']) && !url.contains(':')
+}
+
+/// The local paths of all images in the markdown, in order of first appearance.
+pub fn local_image_paths(markdown: &str, options: Options) -> Vec<String> {
+    let mut paths = Vec::new();
+
+    for event in Parser::new_ext(markdown, options) {
+        if let Event::Start(Tag::Image { dest_url, .. }) = event {
+            if is_local_path(&dest_url) && !paths.iter().any(|path| path == &*dest_url) {
+                paths.push(dest_url.into_string());
+            }
+        }
+    }
+
+    paths
+}
+
+/// The markdown after the pluses delimited metadata block, or all of it if there is none.
+pub fn strip_metadata_block(markdown: &str, options: Options) -> &str {
+    let metadata_end = Parser::new_ext(markdown, options)
+        .into_offset_iter()
+        .find_map(|(event, range)| match event {
+            Event::Start(Tag::MetadataBlock(MetadataBlockKind::PlusesStyle)) => Some(range.end),
+            _ => None,
+        });
+
+    let Some(end) = metadata_end else {
+        return markdown;
+    };
+
+    // The block ends at the closing delimiter, not at its line break
+    let rest = &markdown[end..];
+    rest.strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))
+        .unwrap_or(rest)
+}
+
 fn custom_render_images<'e, 'h, 'g, G, I>(
     iter: I,
     class_name_generator: &'g G,
+    image_urls: &'g HashMap<String, String>,
 ) -> impl Iterator<Item = Event<'e>> + 'h
 where
     'e: 'h,
@@ -299,6 +373,11 @@ where
             }),
             None,
         ) => {
+            let dest_url = match image_urls.get(&*dest_url) {
+                Some(url) => CowStr::from(url.clone()),
+                None => dest_url,
+            };
+
             current_image_block = Some(ImageBlock {
                 dest_url,
                 alt_text: String::new(),
@@ -320,6 +399,136 @@ where
     })
 }
 
+/// Turns heading text into an id: lowercase words joined by dashes, without punctuation.
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+
+    for char in text.chars().flat_map(char::to_lowercase) {
+        if char.is_alphanumeric() || char == '_' {
+            slug.push(char);
+        } else if (char.is_whitespace() || char == '-') && !slug.is_empty() && !slug.ends_with('-')
+        {
+            slug.push('-');
+        }
+    }
+
+    match slug.trim_end_matches('-') {
+        "" => "section".to_string(),
+        slug => slug.to_string(),
+    }
+}
+
+/// Gives headings without an explicit `{#id}` one generated from their text, and appends a
+/// permalink to every heading.
+fn custom_render_headings<'e, G, I>(
+    iter: I,
+    class_name_generator: &G,
+) -> impl Iterator<Item = Event<'e>>
+where
+    G: CssClassNameGenerator,
+    I: Iterator<Item = Event<'e>>,
+{
+    struct HeadingBlock<'a> {
+        id: Option<CowStr<'a>>,
+        classes: Vec<CowStr<'a>>,
+        attrs: Vec<(CowStr<'a>, Option<CowStr<'a>>)>,
+        text: String,
+        events: Vec<Event<'a>>,
+    }
+
+    fn permalink_html(class: Option<&str>, id: &str) -> String {
+        let mut id = id.to_string();
+        escape(&mut id);
+
+        format!(
+            r##"<a{class_clause} href="#{id}" aria-label="Permalink">#</a>"##,
+            class_clause = class
+                .map(|class| format!(r#" class="{class}""#))
+                .unwrap_or_default()
+        )
+    }
+
+    let events: Vec<_> = iter.collect();
+
+    // Explicit ids stay as written, so generated ones have to avoid all of them
+    let mut taken_ids: HashSet<String> = events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Start(Tag::Heading { id: Some(id), .. }) => Some(id.to_string()),
+            _ => None,
+        })
+        .collect();
+    let permalink_class = class_name_generator
+        .class_for_permalink()
+        .map(Cow::into_owned);
+
+    let mut current_heading_block = None;
+
+    events
+        .into_iter()
+        .flat_map(move |event| match (event, &mut current_heading_block) {
+            (
+                Event::Start(Tag::Heading {
+                    level: _,
+                    id,
+                    classes,
+                    attrs,
+                }),
+                None,
+            ) => {
+                current_heading_block = Some(HeadingBlock {
+                    id,
+                    classes,
+                    attrs,
+                    text: String::new(),
+                    events: Vec::new(),
+                });
+                Vec::new()
+            }
+            (Event::End(TagEnd::Heading(level)), Some(_)) => {
+                let HeadingBlock {
+                    id,
+                    classes,
+                    attrs,
+                    text,
+                    events,
+                } = current_heading_block.take().unwrap();
+
+                let id = id.unwrap_or_else(|| {
+                    let slug = slugify(&text);
+                    let id = std::iter::once(slug.clone())
+                        .chain((1..).map(|suffix| format!("{slug}-{suffix}")))
+                        .find(|id| !taken_ids.contains(id))
+                        .unwrap();
+                    taken_ids.insert(id.clone());
+                    id.into()
+                });
+                let permalink = permalink_html(permalink_class.as_deref(), &id);
+
+                let start = Event::Start(Tag::Heading {
+                    level,
+                    id: Some(id),
+                    classes,
+                    attrs,
+                });
+                let end = Event::End(TagEnd::Heading(level));
+
+                std::iter::once(start)
+                    .chain(events)
+                    .chain([Event::Html(permalink.into()), end])
+                    .collect()
+            }
+            (event, Some(HeadingBlock { text, events, .. })) => {
+                if let Event::Text(fragment) | Event::Code(fragment) = &event {
+                    text.push_str(fragment);
+                }
+                events.push(event);
+                Vec::new()
+            }
+            (event, None) => vec![event],
+        })
+}
+
 fn custom_render_code_blocks<'e, 'h, G, I>(
     iter: I,
     highlighter: &'h CodeBlockHighlighter<G>,
@@ -398,36 +607,314 @@ impl<'a, 'e> FromIterator<&'a Event<'e>> for Metadata {
     }
 }
 
+/// A heading of the rendered document, for building a table of contents.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Heading {
+    /// 1 for `<h1>` through 6 for `<h6>`.
+    pub level: u8,
+    /// The plain text of the heading, without any inline markup.
+    pub text: String,
+    /// Only missing in outlines rendered before every heading got an id.
+    pub id: Option<String>,
+}
+
+struct Outline(Vec<Heading>);
+
+impl<'a, 'e> FromIterator<&'a Event<'e>> for Outline {
+    fn from_iter<T: IntoIterator<Item = &'a Event<'e>>>(iter: T) -> Self {
+        let mut headings = Vec::new();
+        let mut current_heading = None;
+
+        for event in iter {
+            match (event, &mut current_heading) {
+                (Event::Start(Tag::Heading { level, id, .. }), None) => {
+                    current_heading = Some(Heading {
+                        level: *level as u8,
+                        text: String::new(),
+                        id: id.as_ref().map(|id| id.to_string()),
+                    });
+                }
+                (Event::End(TagEnd::Heading(_)), Some(_)) => {
+                    headings.extend(current_heading.take());
+                }
+                (Event::Text(text) | Event::Code(text), Some(Heading { text: heading, .. })) => {
+                    heading.push_str(text);
+                }
+                _ => (),
+            }
+        }
+
+        Outline(headings)
+    }
+}
+
+/// Probably slightly low-ball estimate but that's fine, it's a technical blog.
+const AVERAGE_READING_WPM: u64 = 200;
+/// Code takes a lot longer to read than prose, so it's counted by lines instead of words.
+const AVERAGE_CODE_LINES_PER_MINUTE: u64 = 10;
+
+/// How much there is to read in a document.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub struct TextStats {
+    /// Words of prose, including inline code but not code blocks or the metadata block.
+    pub words: u32,
+    /// Non-blank lines in code blocks.
+    pub code_lines: u32,
+}
+
+impl TextStats {
+    /// Estimated time to read the document, rounded up to whole minutes and at least a minute.
+    pub fn reading_time(&self) -> Duration {
+        let words_and_lines = self.words as u64 * AVERAGE_CODE_LINES_PER_MINUTE
+            + self.code_lines as u64 * AVERAGE_READING_WPM;
+        let minutes = words_and_lines.div_ceil(AVERAGE_READING_WPM * AVERAGE_CODE_LINES_PER_MINUTE);
+        Duration::minutes(minutes.max(1) as i64)
+    }
+}
+
+impl<'a, 'e> FromIterator<&'a Event<'e>> for TextStats {
+    fn from_iter<T: IntoIterator<Item = &'a Event<'e>>>(iter: T) -> Self {
+        let mut stats = TextStats::default();
+        let mut in_metadata_block = false;
+        let mut in_code_block = false;
+
+        for event in iter {
+            match event {
+                Event::Start(Tag::MetadataBlock(_)) => in_metadata_block = true,
+                Event::End(TagEnd::MetadataBlock(_)) => in_metadata_block = false,
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(TagEnd::CodeBlock) => in_code_block = false,
+                Event::Text(_) if in_metadata_block => (),
+                Event::Text(code) if in_code_block => {
+                    let lines = code.lines().filter(|line| !line.trim().is_empty()).count();
+                    stats.code_lines += lines as u32;
+                }
+                Event::Text(text) | Event::Code(text) => {
+                    stats.words += text.split_whitespace().count() as u32;
+                }
+                _ => (),
+            }
+        }
+
+        stats
+    }
+}
+
+/// The text of a document without markup or the metadata block, one line per block.
+struct PlainText(String);
+
+impl<'a, 'e> FromIterator<&'a Event<'e>> for PlainText {
+    fn from_iter<T: IntoIterator<Item = &'a Event<'e>>>(iter: T) -> Self {
+        let mut text = String::new();
+        let mut in_metadata_block = false;
+
+        for event in iter {
+            match event {
+                Event::Start(Tag::MetadataBlock(_)) => in_metadata_block = true,
+                Event::End(TagEnd::MetadataBlock(_)) => in_metadata_block = false,
+                Event::Text(_) if in_metadata_block => (),
+                Event::Text(fragment) | Event::Code(fragment) => text.push_str(fragment),
+                Event::SoftBreak | Event::HardBreak => text.push(' '),
+                Event::End(
+                    TagEnd::Paragraph
+                    | TagEnd::Heading(_)
+                    | TagEnd::Item
+                    | TagEnd::CodeBlock
+                    | TagEnd::TableCell,
+                ) if !text.ends_with('\n') => text.push('\n'),
+                _ => (),
+            }
+        }
+
+        PlainText(text.trim_end().to_string())
+    }
+}
+
+/// The automatic excerpt takes paragraphs until it has at least this many words.
+const EXCERPT_WORDS: u32 = 50;
+
+fn is_more_marker(event: &Event) -> bool {
+    match event {
+        Event::Html(html) | Event::InlineHtml(html) => {
+            html.split_whitespace().collect::<String>() == "<!--more-->"
+        }
+        _ => false,
+    }
+}
+
+/// Splits `events` into its top level blocks, like paragraphs, lists or rendered code blocks.
+fn top_level_blocks<'a, 'e>(events: &'a [Event<'e>]) -> Vec<&'a [Event<'e>]> {
+    let mut blocks = Vec::new();
+    let mut depth = 0usize;
+    let mut block_start = 0;
+
+    for (i, event) in events.iter().enumerate() {
+        match event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth -= 1,
+            _ => (),
+        }
+
+        if depth == 0 {
+            blocks.push(&events[block_start..=i]);
+            block_start = i + 1;
+        }
+    }
+
+    blocks
+}
+
+/// The events of `block` before its `<!-- more -->` marker, with the tags still open at the marker
+/// closed.
+fn up_to_more_marker<'e>(block: &[Event<'e>]) -> Vec<Event<'e>> {
+    let mut events = Vec::new();
+    let mut open_tags = Vec::new();
+
+    for event in block.iter().take_while(|event| !is_more_marker(event)) {
+        match event {
+            Event::Start(tag) => open_tags.push(tag.to_end()),
+            Event::End(_) => {
+                open_tags.pop();
+            }
+            _ => (),
+        }
+        events.push(event.clone());
+    }
+
+    // The space before the marker would end up right before the closing tag
+    if let Some(Event::Text(text)) = events.last_mut() {
+        *text = text.trim_end().to_string().into();
+    }
+
+    events.extend(open_tags.into_iter().rev().map(Event::End));
+    events
+}
+
+/// Renders everything before a `<!-- more -->` marker, or otherwise the first paragraphs until
+/// they have [`EXCERPT_WORDS`] words. Headings and the metadata block are always left out, and
+/// links are reduced to their text because post lists link the whole excerpt to the post.
+fn excerpt(events: &[Event]) -> String {
+    let blocks = top_level_blocks(events);
+
+    let is_shown = |block: &[Event]| {
+        !matches!(
+            block.first(),
+            Some(Event::Start(Tag::MetadataBlock(_) | Tag::Heading { .. }))
+        )
+    };
+
+    let excerpt_events: Vec<_> = match blocks
+        .iter()
+        .position(|block| block.iter().any(is_more_marker))
+    {
+        Some(marker) => {
+            let mut excerpt_events: Vec<_> = blocks[..marker]
+                .iter()
+                .filter(|block| is_shown(block))
+                .flat_map(|block| block.iter())
+                .cloned()
+                .collect();
+
+            // A marker inside a paragraph or list ends the excerpt right there
+            let marker_block = blocks[marker];
+            if is_shown(marker_block)
+                && !matches!(marker_block.first(), Some(Event::Start(Tag::HtmlBlock)))
+            {
+                excerpt_events.extend(up_to_more_marker(marker_block));
+            }
+
+            excerpt_events
+        }
+        None => {
+            let mut words = 0;
+            blocks
+                .iter()
+                .filter(|block| matches!(block.first(), Some(Event::Start(Tag::Paragraph))))
+                .take_while(|block| {
+                    let take = words < EXCERPT_WORDS;
+                    words += block.iter().collect::<TextStats>().words;
+                    take
+                })
+                .flat_map(|block| block.iter())
+                .cloned()
+                .collect()
+        }
+    };
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(
+        &mut html,
+        excerpt_events.into_iter().filter(|event| {
+            !matches!(
+                event,
+                Event::Start(Tag::Link { .. }) | Event::End(TagEnd::Link)
+            )
+        }),
+    );
+    html
+}
+
 pub struct RenderResult {
     pub metadata: Option<String>,
     pub html: String,
+    /// All headings in document order.
+    pub outline: Vec<Heading>,
+    pub text_stats: TextStats,
+    /// Html of the start of the document, for showing it in post lists.
+    pub excerpt: String,
+    /// The text of the document without markup, for full text search.
+    pub plain_text: String,
 }
 
+/// `image_urls` maps image urls as written in the markdown to the urls they should be rendered with.
 pub fn render_md_to_html<G: CssClassNameGenerator>(
     markdown: &str,
     options: Options,
     highlighter: &CodeBlockHighlighter<G>,
+    image_urls: &HashMap<String, String>,
 ) -> Result<RenderResult, HighlighterError> {
     let parser = Parser::new_ext(markdown, options);
 
-    let events: Vec<_> = custom_render_code_blocks(
-        custom_render_images(parser, &highlighter.class_name_generator),
-        highlighter,
+    let events: Vec<_> = custom_render_headings(
+        custom_render_images(parser, &highlighter.class_name_generator, image_urls),
+        &highlighter.class_name_generator,
     )
-    .collect::<Result<_, _>>()?;
+    .collect();
+
+    // Code blocks have to be counted before they are replaced with highlighted html.
+    let text_stats = events.iter().collect();
+    let PlainText(plain_text) = events.iter().collect();
+
+    let events: Vec<_> =
+        custom_render_code_blocks(events.into_iter(), highlighter).collect::<Result<_, _>>()?;
 
     let Metadata(metadata) = events.iter().collect();
+    let Outline(outline) = events.iter().collect();
+    let excerpt = excerpt(&events);
 
     let mut html = String::new();
     pulldown_cmark::html::push_html(&mut html, events.into_iter());
 
-    Ok(RenderResult { metadata, html })
+    Ok(RenderResult {
+        metadata,
+        html,
+        outline,
+        text_stats,
+        excerpt,
+        plain_text,
+    })
 }
 
 // TODO: More tests
 #[cfg(test)]
 mod test {
-    use super::escape;
+    use super::{
+        escape, local_image_paths, render_md_to_html, slugify, strip_metadata_block,
+        CodeBlockHighlighter, Heading, TextStats,
+    };
+    use chrono::Duration;
+    use pulldown_cmark::Options;
+    use std::collections::HashMap;
 
     #[test]
     fn test_escape() {
@@ -435,4 +922,194 @@ mod test {
         escape(&mut actual);
         assert_eq!(actual, "abc&lt;&gt;&#39;&quot;&amp;123");
     }
+
+    #[test]
+    fn test_image_urls() {
+        let markdown = "![a](images/a.png) ![b](/static/b.png) ![c](https://example.com/c.png) \
+            ![a again](images/a.png) ![d](./d.jpg)";
+
+        assert_eq!(
+            local_image_paths(markdown, Options::empty()),
+            vec!["images/a.png", "./d.jpg"],
+        );
+
+        let image_urls =
+            HashMap::from([("images/a.png".to_string(), "/media/abc.png".to_string())]);
+        let html = render_md_to_html(
+            markdown,
+            Options::empty(),
+            &CodeBlockHighlighter::standard_config().unwrap(),
+            &image_urls,
+        )
+        .unwrap()
+        .html;
+
+        assert_eq!(html.matches(r#"src="/media/abc.png""#).count(), 2);
+        assert!(html.contains(r#"src="/static/b.png""#));
+        assert!(html.contains(r#"src="./d.jpg""#));
+    }
+
+    #[test]
+    fn test_strip_metadata_block() {
+        let options = Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS;
+
+        assert_eq!(
+            strip_metadata_block("+++\n{\"a\": 1}\n+++\n# Title\n", options),
+            "# Title\n",
+        );
+        assert_eq!(strip_metadata_block("# Title\n", options), "# Title\n");
+    }
+
+    #[test]
+    fn test_outline() {
+        let markdown = "# Intro\n\ntext\n\n\
+            ## The `main` *function* {#main}\n\n\
+            ```rust\n# not a heading\n```\n";
+        let heading = |level, text: &str, id: Option<&str>| Heading {
+            level,
+            text: text.to_string(),
+            id: id.map(str::to_string),
+        };
+
+        let outline = render_md_to_html(
+            markdown,
+            Options::ENABLE_HEADING_ATTRIBUTES,
+            &CodeBlockHighlighter::standard_config().unwrap(),
+            &HashMap::new(),
+        )
+        .unwrap()
+        .outline;
+
+        assert_eq!(
+            outline,
+            vec![
+                heading(1, "Intro", Some("intro")),
+                heading(2, "The main function", Some("main")),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_text_stats() {
+        let markdown = "+++\n{\"title\": \"Not counted\"}\n+++\n\n\
+            # Counting words\n\n\
+            Some *emphasized* and `inline` words.\n\n\
+            ```rust\nfn main() {\n\n    println!(\"one two three\");\n}\n```\n";
+
+        let text_stats = render_md_to_html(
+            markdown,
+            Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS,
+            &CodeBlockHighlighter::standard_config().unwrap(),
+            &HashMap::new(),
+        )
+        .unwrap()
+        .text_stats;
+
+        assert_eq!(
+            text_stats,
+            TextStats {
+                words: 7,
+                code_lines: 3,
+            },
+        );
+        assert_eq!(text_stats.reading_time(), Duration::minutes(1));
+
+        let long = TextStats {
+            words: 401,
+            code_lines: 10,
+        };
+        assert_eq!(long.reading_time(), Duration::minutes(4));
+        assert_eq!(TextStats::default().reading_time(), Duration::minutes(1));
+    }
+
+    #[test]
+    fn test_plain_text() {
+        let markdown = "+++\n{\"title\": \"Not searched\"}\n+++\n\n\
+            # Searching *text*\n\n\
+            Some [linked](https://example.com) and `inline`\nwords.\n\n\
+            ```rust\nfn main() {}\n```\n";
+
+        let plain_text = render_md_to_html(
+            markdown,
+            Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS,
+            &CodeBlockHighlighter::standard_config().unwrap(),
+            &HashMap::new(),
+        )
+        .unwrap()
+        .plain_text;
+
+        assert_eq!(
+            plain_text,
+            "Searching text\nSome linked and inline words.\nfn main() {}"
+        );
+    }
+
+    #[test]
+    fn test_excerpt() {
+        let render = |markdown: &str| {
+            render_md_to_html(
+                markdown,
+                Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS,
+                &CodeBlockHighlighter::standard_config().unwrap(),
+                &HashMap::new(),
+            )
+            .unwrap()
+            .excerpt
+        };
+        let long_paragraph = "word ".repeat(50);
+
+        assert_eq!(
+            render(&format!(
+                "+++\n{{}}\n+++\n\n# Title\n\nFirst.\n\n```\ncode\n```\n\n\
+                {long_paragraph}\n\nLast.\n"
+            )),
+            format!("<p>First.</p>\n<p>{}</p>\n", long_paragraph.trim_end()),
+        );
+        assert_eq!(
+            render("# Title\n\nFirst.\n\n- list\n\n<!-- more -->\n\nLast.\n"),
+            "<p>First.</p>\n<ul>\n<li>list</li>\n</ul>\n",
+        );
+        assert_eq!(
+            render("First [inline <!-- more --> marker](https://example.com).\n\nLast.\n"),
+            "<p>First inline</p>\n",
+        );
+        assert_eq!(
+            render("First.\n\n- one\n- *two <!-- more --> three*\n- four\n"),
+            "<p>First.</p>\n<ul>\n<li>one</li>\n<li><em>two</em></li>\n</ul>\n",
+        );
+        assert_eq!(render("# Only a heading\n"), "");
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Hello, World!"), "hello-world");
+        assert_eq!(slugify("  The `snake_case` -- way "), "the-snake_case-way");
+        assert_eq!(slugify("Über Ärger"), "über-ärger");
+        assert_eq!(slugify("?!"), "section");
+    }
+
+    #[test]
+    fn test_heading_ids() {
+        let markdown = "# Intro\n\n## Intro\n\n## Explicit {#intro-1}\n\n## Intro\n";
+
+        let result = render_md_to_html(
+            markdown,
+            Options::ENABLE_HEADING_ATTRIBUTES,
+            &CodeBlockHighlighter::standard_config().unwrap(),
+            &HashMap::new(),
+        )
+        .unwrap();
+
+        let ids: Vec<_> = result
+            .outline
+            .iter()
+            .map(|heading| heading.id.as_deref().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["intro", "intro-2", "intro-1", "intro-3"]);
+
+        assert!(result.html.contains(
+            r##"<h1 id="intro">Intro<a class="heading-permalink" href="#intro" aria-label="Permalink">#</a></h1>"##
+        ));
+        assert!(result.html.contains(r#"<h2 id="intro-1">Explicit<a "#));
+    }
 }
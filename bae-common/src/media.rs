@@ -0,0 +1,58 @@
+use sha2::{Digest, Sha256};
+
+/// Image types that can be stored as media assets, by file extension.
+const CONTENT_TYPES: [(&str, &str); 7] = [
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("avif", "image/avif"),
+    ("svg", "image/svg+xml"),
+];
+
+/// A file referenced by a post, stored under the hash of its contents.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MediaAsset {
+    /// Hex encoded SHA-256 of `data`.
+    pub hash: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+impl MediaAsset {
+    /// Returns `None` if `extension` is not a supported image type.
+    pub fn new(data: Vec<u8>, extension: &str) -> Option<Self> {
+        let extension = extension.to_ascii_lowercase();
+        let (_, content_type) = CONTENT_TYPES
+            .iter()
+            .find(|(known_extension, _)| *known_extension == extension)?;
+
+        Some(MediaAsset {
+            hash: format!("{:x}", Sha256::digest(&data)),
+            content_type: content_type.to_string(),
+            data,
+        })
+    }
+
+    pub fn file_name(&self) -> String {
+        let extension = CONTENT_TYPES
+            .iter()
+            .find(|(_, content_type)| *content_type == self.content_type)
+            .map_or("bin", |(extension, _)| extension);
+
+        format!("{}.{extension}", self.hash)
+    }
+
+    /// Where the server serves the asset.
+    pub fn path(&self) -> String {
+        format!("/media/{}", self.file_name())
+    }
+}
+
+/// The hash of the asset served at `path`, the inverse of [`MediaAsset::path`].
+pub fn hash_from_path(path: &str) -> Option<&str> {
+    let (hash, _) = path.strip_prefix("/media/")?.split_once('.')?;
+
+    Some(hash)
+}
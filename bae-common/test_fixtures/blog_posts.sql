@@ -1,31 +1,37 @@
-INSERT INTO blog_post (url, title, description, author, markdown, html,
-                       reading_time_minutes, accessible, publication_date)
-VALUES ('public', 'Test (Public)', 'No description', 'Quiet', 'test *bold*', 'test <b>bold</b>',
-        1, false, to_timestamp(0));
-
-INSERT INTO blog_post (url, title, description, author, markdown, html,
-                       reading_time_minutes, accessible, publication_date)
-VALUES ('accessible', 'Test (Accessible)', 'No description', 'Quiet', 'test2', 'test2',
-        1, true, null);
-
-INSERT INTO blog_post (url, title, description, author, markdown, html,
-                       reading_time_minutes, accessible, publication_date)
-VALUES ('not_accessible', 'Test (Not Accessible)', 'No description', 'Quiet', 'test3', 'test3',
-        1, false, null);
-
-INSERT INTO blog_post (url, title, description, author, markdown, html,
-                       reading_time_minutes, accessible, publication_date)
-VALUES ('public_in_future', 'Test (Public in future)', 'No description', 'Quiet', 'test4', 'test4',
-        1, false, to_timestamp(10_000_000_000));
-
-INSERT INTO blog_post (url, title, description, author, markdown, html,
-                       reading_time_minutes, accessible, publication_date)
-VALUES ('accessible_public_in_future', 'Test (Accessible, Public in future)', 'No description', 'Quiet', 'test5', 'test5',
-        1, true, to_timestamp(10_000_000_001));
-
-INSERT INTO blog_post (url, title, description, author, markdown, html,
-                       reading_time_minutes, accessible, publication_date)
-VALUES ('long_post', 'Test (Longer blog post)', 'No description', 'gpluscb',
+INSERT INTO blog_post (url, title, description, markdown, html, plain_text,
+                       reading_time_minutes, status, publication_date, updated_at, language,
+                       translation_key)
+VALUES ('public', 'Test (Public)', 'No description', 'test *bold*', 'test <b>bold</b>', 'test bold',
+        1, 'published', to_timestamp(0), to_timestamp(86400), 'en-gb', 'test');
+
+INSERT INTO blog_post (url, title, description, markdown, html,
+                       reading_time_minutes, status, publication_date, updated_at, language,
+                       translation_key)
+VALUES ('accessible', 'Test (Accessible)', 'No description', 'test2', 'test2',
+        1, 'unlisted', null, to_timestamp(0), 'de', 'test');
+
+INSERT INTO blog_post (url, title, description, markdown, html,
+                       reading_time_minutes, status, publication_date, updated_at, language,
+                       translation_key)
+VALUES ('not_accessible', 'Test (Not Accessible)', 'No description', 'test3', 'test3',
+        1, 'draft', null, to_timestamp(0), 'en-gb', null);
+
+INSERT INTO blog_post (url, title, description, markdown, html,
+                       reading_time_minutes, status, publication_date, updated_at, language,
+                       translation_key)
+VALUES ('public_in_future', 'Test (Public in future)', 'No description', 'test4', 'test4',
+        1, 'scheduled', to_timestamp(10_000_000_000), to_timestamp(0), 'en-gb', null);
+
+INSERT INTO blog_post (url, title, description, markdown, html,
+                       reading_time_minutes, status, publication_date, updated_at, language,
+                       translation_key)
+VALUES ('accessible_public_in_future', 'Test (Accessible, Public in future)', 'No description', 'test5', 'test5',
+        1, 'scheduled', to_timestamp(10_000_000_001), to_timestamp(0), 'en-gb', null);
+
+INSERT INTO blog_post (url, title, description, markdown, html,
+                       reading_time_minutes, status, publication_date, updated_at, language,
+                       translation_key)
+VALUES ('long_post', 'Test (Longer blog post)', 'No description',
         e'Velit quod itaque autem et. Repellendus magnam aut repudiandae quisquam veritatis. At quaerat delectus quis eligendi. Consequuntur aliquam nemo vitae hic dolor soluta qui dicta. Sapiente labore sunt sint iusto non fugiat et tenetur.
 
 Unde temporibus mollitia qui qui assumenda culpa voluptatem. Repellat dignissimos nihil autem excepturi dolorem ut et. Et et accusamus ex voluptates fuga porro adipisci. Nulla voluptates vel suscipit et libero.
@@ -223,4 +229,13 @@ Quis qui modi qui sed. Impedit commodi temporibus vero aliquam vero eveniet quid
 Ab incidunt minus esse dolorem. Dolor eius et suscipit quia aut voluptatem quia inventore. Et architecto natus sit fugit veritatis odit odit eum. A sint iusto praesentium doloremque.
 
 Perspiciatis voluptatem iste possimus. Blanditiis culpa similique minus est nihil odio. Non occaecati qui aliquam libero assumenda. Reiciendis cumque molestiae in.',
-        60, true, to_timestamp(1));
+        60, 'published', to_timestamp(1), to_timestamp(0), 'en-gb', null);
+
+INSERT INTO post_author (url, author, position)
+VALUES ('public', 'Quiet', 0),
+       ('accessible', 'Quiet', 0),
+       ('not_accessible', 'Quiet', 0),
+       ('public_in_future', 'Quiet', 0),
+       ('accessible_public_in_future', 'Quiet', 0),
+       ('long_post', 'gpluscb', 0),
+       ('long_post', 'Quiet', 1);
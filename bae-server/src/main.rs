@@ -9,12 +9,14 @@ use axum::BoxError;
 use axum_server::tls_rustls::RustlsConfig;
 use axum_server::Handle;
 use bae_common::database;
+use bae_common::database::BlogStore;
 use bae_common::markdown_render::{CodeBlockHighlighter, StandardClassNameGenerator};
 use serde::Deserialize;
-use sqlx::PgPool;
+use server::cache::{self, PageCache};
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::signal;
 use tower_http::trace::TraceLayer;
@@ -34,6 +36,12 @@ struct Env {
     static_path: PathBuf,
     tls_cert_path: PathBuf,
     tls_key_path: PathBuf,
+    #[serde(default = "default_rss_item_limit")]
+    rss_item_limit: u32,
+}
+
+fn default_rss_item_limit() -> u32 {
+    20
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -45,10 +53,16 @@ struct Ports {
 #[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
 pub struct BaseUri(pub String);
 
+/// The maximum number of items in the RSS channel.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Debug)]
+pub struct RssItemLimit(pub u32);
+
 #[derive(Clone, FromRef)]
 pub struct AppState {
-    database: PgPool,
+    database: Arc<dyn BlogStore>,
+    page_cache: Arc<PageCache>,
     base_uri: BaseUri,
+    rss_item_limit: RssItemLimit,
 }
 
 #[tokio::main]
@@ -75,17 +89,33 @@ async fn main() {
         .await
         .expect("Parsing certificate from pem file failed");
 
-    let database = PgPool::connect(&env.database_url)
+    let database = database::connect(&env.database_url)
         .await
         .expect("Could not connect to database");
 
-    database::migrate(&database)
-        .await
-        .expect("Database migration failed");
+    database.migrate().await.expect("Database migration failed");
+
+    let page_cache = match database::listen_for_blog_post_changes(&env.database_url).await {
+        Ok(listener) => {
+            let page_cache = Arc::new(PageCache::new());
+            tokio::spawn(cache::invalidate_on_notifications(
+                listener,
+                page_cache.clone(),
+            ));
+            page_cache
+        }
+        Err(database::Error::Unsupported) => {
+            info!("Database can't notify about changes, not caching pages");
+            Arc::new(PageCache::disabled())
+        }
+        Err(error) => panic!("Listening for blog post changes failed: {error}"),
+    };
 
     let app_state = AppState {
         database,
+        page_cache,
         base_uri: BaseUri(env.base_uri),
+        rss_item_limit: RssItemLimit(env.rss_item_limit),
     };
 
     let handle = Handle::new();
@@ -1,7 +1,7 @@
-use crate::server::blog::{BlogPostPath, TaggedPath};
+use crate::server::blog::{AuthorPath, BlogPostPath, SeriesPath, TaggedPath};
 use axum_extra::routing::TypedPath;
 use bae_common::blog::BlogPost;
-use bae_common::database::Tag;
+use bae_common::database::{Author, Series, Tag};
 
 pub trait ServerPathExt {
     type Path: TypedPath;
@@ -26,3 +26,23 @@ impl ServerPathExt for Tag {
         TaggedPath { tag: self.clone() }
     }
 }
+
+impl ServerPathExt for Series {
+    type Path = SeriesPath;
+
+    fn full_path(&self) -> Self::Path {
+        SeriesPath {
+            series: self.clone(),
+        }
+    }
+}
+
+impl ServerPathExt for Author {
+    type Path = AuthorPath;
+
+    fn full_path(&self) -> Self::Path {
+        AuthorPath {
+            author: self.clone(),
+        }
+    }
+}
@@ -1,63 +1,278 @@
 pub mod templates;
 
 use crate::model::ServerPathExt;
+use crate::server::cache::PageCache;
 use crate::server::util::Xml;
 use crate::server::{Error, Result};
-use crate::{AppState, BaseUri};
+use crate::{AppState, BaseUri, RssItemLimit};
 use askama::Template;
 use axum::extract::Request;
 use axum::extract::State;
-use axum::response::Html;
+use axum::http::header::{CACHE_CONTROL, LOCATION, REFERRER_POLICY};
+use axum::http::{HeaderName, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
 use axum::Router;
 use axum_extra::extract::Query;
 use axum_extra::routing::{RouterExt, TypedPath};
-use bae_common::database;
-use bae_common::database::{Author, Tag};
+use bae_common::blog::BlogPost;
+use bae_common::database::{
+    Author, BlogStore, Language, PageBoundary, PostKey, PostSelection, Series, Tag,
+};
+use rss::extension::dublincore::DublinCoreExtensionBuilder;
 use rss::{CategoryBuilder, ChannelBuilder, GuidBuilder, ItemBuilder, SourceBuilder};
 use serde::{Deserialize, Serialize};
 use sqlx::types::chrono::DateTime;
-use sqlx::PgPool;
-use templates::{BlogPostTemplate, HomeTemplate, TaggedTemplate, TagsTemplate};
+use std::sync::Arc;
+use templates::{
+    AuthorNames, AuthorTemplate, BlogPostTemplate, HomeTemplate, LanguageLinks, PageLinks,
+    SearchTemplate, SeriesNavigation, SeriesTemplate, TableOfContents, TaggedTemplate,
+    TagsTemplate,
+};
+
+const POSTS_PER_PAGE: u32 = 10;
+
+/// The featured sections show only the newest featured posts.
+const MAX_FEATURED_POSTS: u32 = 3;
 
 pub fn router() -> Router<AppState> {
     Router::new()
         .typed_get(home)
         .typed_get(blog_post)
         .typed_get(tagged)
+        .typed_get(author)
+        .typed_get(series)
+        .typed_get(search)
         .typed_get(tags)
         .typed_get(rss)
 }
 
-#[derive(Default, TypedPath, Deserialize)]
+#[derive(Clone, Default, TypedPath, Deserialize)]
 #[typed_path("/blog", rejection(Error))]
 pub struct HomePath {}
 
-pub async fn home(HomePath {}: HomePath, State(database): State<PgPool>) -> Result<Html<String>> {
-    let blog_posts = database::get_blog_posts(None, None, true, &database).await?;
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct PageQueryParams {
+    /// Start the page after this post.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<PostKey>,
+    /// End the page before this post.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<PostKey>,
+    /// Only list posts in this language.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lang: Option<Language>,
+}
+
+impl PageQueryParams {
+    fn boundary(&self) -> Option<PageBoundary> {
+        match (&self.page, &self.before) {
+            (Some(after), _) => Some(PageBoundary::After(after.clone())),
+            (None, Some(before)) => Some(PageBoundary::Before(before.clone())),
+            (None, None) => None,
+        }
+    }
 
-    let html = HomeTemplate { blog_posts }.render()?;
+    fn languages(&self) -> Option<&[Language]> {
+        self.lang.as_ref().map(std::slice::from_ref)
+    }
+}
+
+pub async fn home(
+    HomePath {}: HomePath,
+    Query(params): Query<PageQueryParams>,
+    State(database): State<Arc<dyn BlogStore>>,
+    State(page_cache): State<Arc<PageCache>>,
+) -> Result<Html<String>> {
+    let miss = match page_cache.get(HomePath {}.with_query_params(&params).to_string()) {
+        Ok(html) => return Ok(Html(html)),
+        Err(miss) => miss,
+    };
+
+    let boundary = params.boundary();
+    let featured_posts = featured_posts(&*database, params.languages()).await?;
+
+    // Featured posts are shown above the first page instead, so no page lists them. Every page
+    // takes that many more posts to stay full, which keeps the pages the same in both directions.
+    let mut page = database
+        .get_blog_posts_page(
+            None,
+            None,
+            params.languages(),
+            true,
+            PostSelection::PinnedFirst,
+            boundary.as_ref(),
+            POSTS_PER_PAGE + featured_posts.len() as u32,
+        )
+        .await?;
+
+    page.blog_posts.retain(|post| {
+        !featured_posts
+            .iter()
+            .any(|featured| featured.url == post.url)
+    });
+
+    let excess = page
+        .blog_posts
+        .len()
+        .saturating_sub(POSTS_PER_PAGE as usize);
+    if excess > 0 {
+        if let Some(PageBoundary::Before(_)) = boundary {
+            page.blog_posts.drain(..excess);
+            page.previous = page.blog_posts.first().map(PostKey::of);
+        } else {
+            page.blog_posts.truncate(POSTS_PER_PAGE as usize);
+            page.next = page.blog_posts.last().map(PostKey::of);
+        }
+    }
+
+    // The first page can also be reached by paging back, so it is told apart by its contents
+    let featured_posts = match page.previous {
+        None => featured_posts,
+        Some(_) => Vec::new(),
+    };
+
+    let languages = database.get_languages(true).await?;
+
+    let page_links = PageLinks::new(HomePath {}, &page, params.lang.clone());
+    let language_links = LanguageLinks::new(HomePath {}, languages, params.lang);
+    let html = HomeTemplate {
+        featured_posts,
+        blog_posts: page.blog_posts,
+        page_links,
+        language_links,
+        author_names: AuthorNames::new(database.get_authors().await?),
+    }
+    .render()?;
+
+    page_cache.insert(miss, html.clone(), &*database).await;
     Ok(Html(html))
 }
 
+/// The newest featured posts, for the featured sections of the blog home and the site home.
+pub async fn featured_posts(
+    database: &dyn BlogStore,
+    languages: Option<&[Language]>,
+) -> Result<Vec<BlogPost>> {
+    let page = database
+        .get_blog_posts_page(
+            None,
+            None,
+            languages,
+            true,
+            PostSelection::FeaturedOnly,
+            None,
+            MAX_FEATURED_POSTS,
+        )
+        .await?;
+
+    Ok(page.blog_posts)
+}
+
 #[derive(TypedPath, Deserialize)]
 #[typed_path("/blog/{post_url}", rejection(Error))]
 pub struct BlogPostPath {
     pub post_url: String,
 }
 
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct PreviewQueryParams {
+    /// Preview token giving access to a post that is not public yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preview: Option<String>,
+}
+
+const X_ROBOTS_TAG: HeaderName = HeaderName::from_static("x-robots-tag");
+
 pub async fn blog_post(
     BlogPostPath { post_url }: BlogPostPath,
-    State(database): State<PgPool>,
-) -> Result<Html<String>> {
-    let blog_post = database::get_blog_post(&post_url, true, &database)
-        .await?
-        .ok_or(Error::NotFound)?;
+    Query(params): Query<PreviewQueryParams>,
+    State(database): State<Arc<dyn BlogStore>>,
+    State(page_cache): State<Arc<PageCache>>,
+    State(BaseUri(base_uri)): State<BaseUri>,
+) -> Result<Response> {
+    let key = BlogPostPath {
+        post_url: post_url.clone(),
+    }
+    .with_query_params(&params)
+    .to_string();
+    let miss = match page_cache.get(key) {
+        Ok(html) => return Ok(Html(html).into_response()),
+        Err(miss) => miss,
+    };
 
-    let html = BlogPostTemplate { blog_post }.render()?;
-    Ok(Html(html))
+    let Some(blog_post) = database.get_blog_post(&post_url, false).await? else {
+        // The post might have been moved to a different url
+        let new_url = database
+            .get_url_redirect(&post_url)
+            .await?
+            .ok_or(Error::NotFound)?;
+
+        let location = BlogPostPath { post_url: new_url }
+            .with_query_params(params)
+            .to_string();
+        return Ok((StatusCode::MOVED_PERMANENTLY, [(LOCATION, location)]).into_response());
+    };
+
+    let is_preview = !blog_post.is_public();
+    if is_preview {
+        // Non-public posts are only served with a valid preview token
+        let Some(token) = &params.preview else {
+            return Err(Error::NotFound);
+        };
+
+        if !database
+            .is_valid_preview_token(&blog_post.url, token)
+            .await?
+        {
+            return Err(Error::NotFound);
+        }
+    }
+
+    let series = match &blog_post.series {
+        Some(series_part) => {
+            let series_posts = database.get_series_posts(&series_part.series, true).await?;
+            Some(SeriesNavigation::new(series_part, series_posts))
+        }
+        None => None,
+    };
+
+    let translations = match &blog_post.translation_key {
+        Some(translation_key) => database
+            .get_translations(translation_key, true)
+            .await?
+            .into_iter()
+            .filter(|translation| translation.url != blog_post.url)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let table_of_contents = TableOfContents::new(&blog_post);
+
+    let html = BlogPostTemplate {
+        blog_post,
+        series,
+        table_of_contents,
+        translations,
+        base_uri,
+        author_names: AuthorNames::new(database.get_authors().await?),
+    }
+    .render()?;
+
+    if is_preview {
+        // Keep previews out of search engines, caches and referrers
+        let headers = [
+            (X_ROBOTS_TAG, "noindex, nofollow"),
+            (CACHE_CONTROL, "private, no-store"),
+            (REFERRER_POLICY, "no-referrer"),
+        ];
+        return Ok((headers, Html(html)).into_response());
+    }
+
+    page_cache.insert(miss, html.clone(), &*database).await;
+    Ok(Html(html).into_response())
 }
 
-#[derive(TypedPath, Deserialize)]
+#[derive(Clone, TypedPath, Deserialize)]
 #[typed_path("/blog/tagged/{tag}", rejection(Error))]
 pub struct TaggedPath {
     pub tag: Tag,
@@ -65,11 +280,133 @@ pub struct TaggedPath {
 
 pub async fn tagged(
     TaggedPath { tag }: TaggedPath,
-    State(database): State<PgPool>,
+    Query(params): Query<PageQueryParams>,
+    State(database): State<Arc<dyn BlogStore>>,
+) -> Result<Html<String>> {
+    let page = database
+        .get_blog_posts_page(
+            None,
+            Some(std::slice::from_ref(&tag)),
+            params.languages(),
+            true,
+            PostSelection::All,
+            params.boundary().as_ref(),
+            POSTS_PER_PAGE,
+        )
+        .await?;
+
+    let page_links = PageLinks::new(TaggedPath { tag: tag.clone() }, &page, params.lang);
+    let html = TaggedTemplate {
+        tag,
+        blog_posts: page.blog_posts,
+        page_links,
+        author_names: AuthorNames::new(database.get_authors().await?),
+    }
+    .render()?;
+    Ok(Html(html))
+}
+
+#[derive(Clone, TypedPath, Deserialize)]
+#[typed_path("/blog/author/{author}", rejection(Error))]
+pub struct AuthorPath {
+    pub author: Author,
+}
+
+pub async fn author(
+    AuthorPath { author }: AuthorPath,
+    Query(params): Query<PageQueryParams>,
+    State(database): State<Arc<dyn BlogStore>>,
+) -> Result<Html<String>> {
+    let author_profile = database.get_author(&author).await?.ok_or(Error::NotFound)?;
+
+    let page = database
+        .get_blog_posts_page(
+            Some(std::slice::from_ref(&author)),
+            None,
+            params.languages(),
+            true,
+            PostSelection::All,
+            params.boundary().as_ref(),
+            POSTS_PER_PAGE,
+        )
+        .await?;
+
+    let page_links = PageLinks::new(
+        AuthorPath {
+            author: author.clone(),
+        },
+        &page,
+        params.lang.clone(),
+    );
+    let rss_path = RssPath {}
+        .with_query_params(RssQueryParams {
+            tags: Vec::new(),
+            authors: vec![author],
+            languages: params.lang.into_iter().collect(),
+        })
+        .to_string();
+
+    let html = AuthorTemplate {
+        author_profile,
+        blog_posts: page.blog_posts,
+        page_links,
+        rss_path,
+        author_names: AuthorNames::new(database.get_authors().await?),
+    }
+    .render()?;
+    Ok(Html(html))
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/blog/series/{series}", rejection(Error))]
+pub struct SeriesPath {
+    pub series: Series,
+}
+
+pub async fn series(
+    SeriesPath { series }: SeriesPath,
+    State(database): State<Arc<dyn BlogStore>>,
+) -> Result<Html<String>> {
+    let blog_posts = database.get_series_posts(&series, true).await?;
+
+    let html = SeriesTemplate {
+        series,
+        blog_posts,
+        author_names: AuthorNames::new(database.get_authors().await?),
+    }
+    .render()?;
+    Ok(Html(html))
+}
+
+#[derive(Default, TypedPath, Deserialize)]
+#[typed_path("/blog/search", rejection(Error))]
+pub struct SearchPath {}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SearchQueryParams {
+    #[serde(default)]
+    q: String,
+}
+
+pub async fn search(
+    SearchPath {}: SearchPath,
+    Query(SearchQueryParams { q }): Query<SearchQueryParams>,
+    State(database): State<Arc<dyn BlogStore>>,
 ) -> Result<Html<String>> {
-    let blog_posts = database::get_blog_posts(None, Some(&[tag.clone()]), true, &database).await?;
+    let query = q.trim().to_string();
 
-    let html = TaggedTemplate { tag, blog_posts }.render()?;
+    let results = if query.is_empty() {
+        Vec::new()
+    } else {
+        database.search_blog_posts(&query, true).await?
+    };
+
+    let html = SearchTemplate {
+        query,
+        results,
+        author_names: AuthorNames::new(database.get_authors().await?),
+    }
+    .render()?;
     Ok(Html(html))
 }
 
@@ -77,8 +414,11 @@ pub async fn tagged(
 #[typed_path("/blog/tags", rejection(Error))]
 pub struct TagsPath {}
 
-pub async fn tags(TagsPath {}: TagsPath, State(database): State<PgPool>) -> Result<Html<String>> {
-    let tags = database::get_tags(true, &database).await?;
+pub async fn tags(
+    TagsPath {}: TagsPath,
+    State(database): State<Arc<dyn BlogStore>>,
+) -> Result<Html<String>> {
+    let tags = database.get_tags(true).await?;
 
     let html = TagsTemplate { tags }.render()?;
     Ok(Html(html))
@@ -94,27 +434,57 @@ pub struct RssQueryParams {
     tags: Vec<Tag>,
     #[serde(default)]
     authors: Vec<Author>,
+    #[serde(default)]
+    languages: Vec<Language>,
 }
 
 pub async fn rss(
     RssPath {}: RssPath,
     Query(params): Query<RssQueryParams>,
-    State(database): State<PgPool>,
+    State(database): State<Arc<dyn BlogStore>>,
+    State(page_cache): State<Arc<PageCache>>,
     State(BaseUri(base_uri)): State<BaseUri>,
+    State(RssItemLimit(rss_item_limit)): State<RssItemLimit>,
     request: Request,
 ) -> Result<Xml<String>> {
+    let miss = match page_cache.get(RssPath {}.with_query_params(&params).to_string()) {
+        Ok(xml) => return Ok(Xml(xml)),
+        Err(miss) => miss,
+    };
+
     let current_path = request.uri().path();
 
     let tags = (!params.tags.is_empty()).then_some(params.tags);
     let authors = (!params.authors.is_empty()).then_some(params.authors);
+    // A feed restricted to a single language is entirely in that language
+    let channel_language = match params.languages.as_slice() {
+        [language] => language.0.clone(),
+        _ => "en-gb".to_string(),
+    };
+    let languages = (!params.languages.is_empty()).then_some(params.languages);
 
-    let blog_posts =
-        database::get_blog_posts(authors.as_deref(), tags.as_deref(), true, &database).await?;
+    let blog_posts = database
+        .get_blog_posts_page(
+            authors.as_deref(),
+            tags.as_deref(),
+            languages.as_deref(),
+            true,
+            PostSelection::All,
+            None,
+            rss_item_limit,
+        )
+        .await?
+        .blog_posts;
 
     // Note: chrono's rfc2822 date time is RSS compatible as RSS explicitly allows quadruple digit years
+    // Scheduled posts enter the feed at their publication date, which can be after their last update
     let last_update = blog_posts
-        .first()
-        .and_then(|post| post.publication_date)
+        .iter()
+        .map(|post| {
+            post.publication_date
+                .map_or(post.updated_at, |date| date.max(post.updated_at))
+        })
+        .max()
         .as_ref()
         .map(DateTime::to_rfc2822);
 
@@ -124,7 +494,7 @@ pub async fn rss(
         .title("Bae")
         .link(format!("{base_uri}{}", HomePath {}))
         .description("The RSS feed for the blog part of bae (blog and eh).")
-        .language("en-gb".to_string())
+        .language(channel_language)
         .managing_editor("marrueeee@gmail.com".to_string())
         .webmaster("marrueeee@gmail.com".to_string())
         .last_build_date(last_update)
@@ -135,6 +505,7 @@ pub async fn rss(
         .ttl((60 * 24).to_string())
         .image(None); // TODO
 
+    let author_names = AuthorNames::new(database.get_authors().await?);
     for blog_post in blog_posts {
         let full_url = format!("{base_uri}{}", blog_post.full_path());
 
@@ -154,6 +525,8 @@ pub async fn rss(
             .permalink(true)
             .build();
 
+        let authors = author_names.join(&blog_post.authors);
+
         let pub_date = blog_post
             .publication_date
             .ok_or(Error::InvalidDatabaseReturn)?
@@ -164,20 +537,400 @@ pub async fn rss(
             .title("Bae".to_string())
             .build();
 
+        let dublin_core = DublinCoreExtensionBuilder::default()
+            .languages(vec![blog_post.language.0])
+            .build();
+
+        let stats = format!(
+            "({} words, {} minute read)",
+            blog_post.word_count,
+            blog_post.reading_time.num_minutes(),
+        );
+        let description = match (blog_post.description, blog_post.word_count) {
+            (description, 0) => description.unwrap_or(blog_post.excerpt),
+            (Some(description), _) => format!("{description} {stats}"),
+            // The stats belong in the last paragraph of the excerpt, not after it
+            (None, _) => {
+                let excerpt = blog_post.excerpt.trim_end();
+                match excerpt.strip_suffix("</p>") {
+                    Some(paragraph) => format!("{paragraph} {stats}</p>"),
+                    None => format!("{excerpt}<p>{stats}</p>"),
+                }
+            }
+        };
+
         let item = ItemBuilder::default()
             .title(blog_post.title)
             .link(full_url)
-            .description(blog_post.description)
-            .author(blog_post.author.0)
+            .description(description)
+            .author(authors)
             .categories(categories)
             .guid(guid)
             .pub_date(pub_date)
             .source(source)
             .content(blog_post.html)
+            .dublin_core_ext(dublin_core)
             .build();
 
         channel.item(item);
     }
 
-    Ok(Xml(channel.build().to_string()))
+    let xml = channel.build().to_string();
+    page_cache.insert(miss, xml.clone(), &*database).await;
+    Ok(Xml(xml))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::X_ROBOTS_TAG;
+    use crate::server::cache::PageCache;
+    use crate::{server, AppState, BaseUri, RssItemLimit};
+    use axum::body::{to_bytes, Body};
+    use axum::http::header::LOCATION;
+    use axum::http::{Request, StatusCode};
+    use axum::response::Response;
+    use bae_common::blog::{AuthorProfile, BlogPost};
+    use bae_common::database::{Author, BlogStore, MemoryStore, PostStatus};
+    use bae_common::markdown_render::Heading;
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn store() -> Arc<MemoryStore> {
+        let store = Arc::new(MemoryStore::new());
+        for blog_post in [
+            BlogPost::test_post("public", PostStatus::Published, Some(0)),
+            BlogPost::test_post("draft", PostStatus::Draft, None),
+        ] {
+            store.insert_blog_post(&blog_post, &[]).await.unwrap();
+        }
+
+        store
+    }
+
+    async fn get(store: Arc<MemoryStore>, uri: &str) -> (Response, String) {
+        get_cached(store, Arc::new(PageCache::disabled()), uri).await
+    }
+
+    async fn get_cached(
+        store: Arc<MemoryStore>,
+        page_cache: Arc<PageCache>,
+        uri: &str,
+    ) -> (Response, String) {
+        let app_state = AppState {
+            database: store,
+            page_cache,
+            base_uri: BaseUri("https://example.com".to_string()),
+            rss_item_limit: RssItemLimit(20),
+        };
+        let app = server::router("static").with_state(app_state);
+
+        let response = app
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let (parts, body) = response.into_parts();
+        let body = to_bytes(body, usize::MAX).await.unwrap();
+
+        (
+            Response::from_parts(parts, Body::empty()),
+            String::from_utf8(body.to_vec()).unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn home_test() {
+        let (response, body) = get(store().await, "/blog").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body.contains("Title of public"));
+        assert!(!body.contains("Title of draft"));
+    }
+
+    #[tokio::test]
+    async fn table_of_contents_test() {
+        let store = store().await;
+        let heading = |level, text: &str, id: Option<&str>| Heading {
+            level,
+            text: text.to_string(),
+            id: id.map(str::to_string),
+        };
+        let post = BlogPost {
+            outline: vec![
+                heading(2, "First", Some("first")),
+                heading(3, "Nested", None),
+            ],
+            ..BlogPost::test_post("outlined", PostStatus::Published, Some(0))
+        };
+        store.insert_blog_post(&post, &[]).await.unwrap();
+
+        // Only shown when enabled in the front matter
+        let (_, body) = get(store.clone(), "/blog/outlined").await;
+        assert!(!body.contains("blog-post-table-of-contents"));
+
+        let post = BlogPost {
+            table_of_contents: true,
+            ..post
+        };
+        store
+            .update_blog_post(None, &post, &post.authors[0], &[])
+            .await
+            .unwrap();
+
+        let (_, body) = get(store, "/blog/outlined").await;
+        assert!(body.contains("blog-post-table-of-contents"));
+        assert!(body.contains(r##"<a href="#first">First</a>"##));
+        assert!(body.contains(r#"<li style="margin-left: 1em">"#));
+        assert!(body.contains("Nested"));
+    }
+
+    #[tokio::test]
+    async fn featured_test() {
+        let store = store().await;
+        assert!(!get(store.clone(), "/").await.1.contains("featured-posts"));
+
+        let featured = BlogPost {
+            featured: true,
+            ..BlogPost::test_post("featured", PostStatus::Published, Some(0))
+        };
+        store.insert_blog_post(&featured, &[]).await.unwrap();
+
+        for uri in ["/", "/blog"] {
+            let (response, body) = get(store.clone(), uri).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(body.contains("featured-posts"));
+            assert!(body.contains("Title of featured"));
+        }
+
+        // Only the newest are featured, and the first page does not list them again
+        for day in 1..=4 {
+            let featured = BlogPost {
+                featured: true,
+                ..BlogPost::test_post(
+                    &format!("featured_{day}"),
+                    PostStatus::Published,
+                    Some(day * 24 * 60 * 60),
+                )
+            };
+            store.insert_blog_post(&featured, &[]).await.unwrap();
+        }
+
+        let (_, body) = get(store.clone(), "/").await;
+        assert!(body.contains("Title of featured_4"));
+        assert!(!body.contains("Title of featured_1"));
+
+        let (_, body) = get(store, "/blog").await;
+        assert_eq!(body.matches("\"/blog/featured_4\"").count(), 1);
+        assert_eq!(body.matches("\"/blog/featured_1\"").count(), 1);
+        assert!(body.contains("Title of public"));
+    }
+
+    #[tokio::test]
+    async fn featured_paging_test() {
+        let store = store().await;
+        for day in 1..=12 {
+            let post = BlogPost {
+                featured: day > 10,
+                ..BlogPost::test_post(
+                    &format!("post_{day}"),
+                    PostStatus::Published,
+                    Some(day * 24 * 60 * 60),
+                )
+            };
+            store.insert_blog_post(&post, &[]).await.unwrap();
+        }
+
+        let link = |body: &str, text: &str| {
+            let end = body.find(&format!("\">{text}</a>")).unwrap();
+            let start = body[..end].rfind("href=\"").unwrap() + "href=\"".len();
+            body[start..end].replace("&amp;", "&")
+        };
+
+        // Paging forwards and back again ends on the same first page, with the featured posts
+        let (_, first) = get(store.clone(), "/blog").await;
+        let (_, second) = get(store.clone(), &link(&first, "Older posts")).await;
+        assert!(second.contains("Title of public"));
+        assert!(!second.contains("featured-posts"));
+        let (_, back) = get(store, &link(&second, "Newer posts")).await;
+        assert_eq!(back, first);
+        assert!(back.contains("featured-posts"));
+        assert_eq!(back.matches("\"/blog/post_12\"").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn excerpt_test() {
+        let store = store().await;
+        let without_description = BlogPost {
+            description: None,
+            excerpt: "<p>Excerpt of excerpt</p>".to_string(),
+            ..BlogPost::test_post("excerpt", PostStatus::Published, Some(0))
+        };
+        store
+            .insert_blog_post(&without_description, &[])
+            .await
+            .unwrap();
+
+        let (_, body) = get(store.clone(), "/blog").await;
+        assert!(body.contains("<p>Excerpt of excerpt</p>"));
+        assert!(body.contains("Description of public"));
+
+        let listed = BlogPost {
+            description: None,
+            excerpt: "<ul>\n<li>Listed</li>\n</ul>\n".to_string(),
+            ..BlogPost::test_post("listed", PostStatus::Published, Some(0))
+        };
+        store.insert_blog_post(&listed, &[]).await.unwrap();
+
+        // The stats go into the last paragraph of an excerpt, or after it in one of their own
+        let (_, body) = get(store, "/blog/rss.xml").await;
+        assert!(body.contains("<p>Excerpt of excerpt (3 words, 1 minute read)</p>"));
+        assert!(body.contains("</ul><p>(3 words, 1 minute read)</p>"));
+    }
+
+    #[tokio::test]
+    async fn page_cache_test() {
+        let store = store().await;
+        let page_cache = Arc::new(PageCache::new());
+        let get = |uri| get_cached(store.clone(), page_cache.clone(), uri);
+
+        let (_, body) = get("/blog").await;
+        assert!(!body.contains("Title of new"));
+
+        // Changes only show up once the cache is invalidated
+        let new = BlogPost::test_post("new", PostStatus::Published, Some(0));
+        store.insert_blog_post(&new, &[]).await.unwrap();
+        assert!(!get("/blog").await.1.contains("Title of new"));
+        page_cache.invalidate();
+        assert!(get("/blog").await.1.contains("Title of new"));
+
+        // Unknown query parameters don't make a new page
+        let newer = BlogPost::test_post("newer", PostStatus::Published, Some(1));
+        store.insert_blog_post(&newer, &[]).await.unwrap();
+        assert!(!get("/blog?utm_source=feed")
+            .await
+            .1
+            .contains("Title of newer"));
+        page_cache.invalidate();
+
+        // Scheduled posts show up at their publication date without an invalidation
+        let scheduled = BlogPost {
+            publication_date: Some(Utc::now() + Duration::milliseconds(500)),
+            ..BlogPost::test_post("scheduled", PostStatus::Scheduled, Some(0))
+        };
+        store.insert_blog_post(&scheduled, &[]).await.unwrap();
+        page_cache.invalidate();
+        assert!(!get("/blog").await.1.contains("Title of scheduled"));
+        tokio::time::sleep(std::time::Duration::from_millis(600)).await;
+        assert!(get("/blog").await.1.contains("Title of scheduled"));
+
+        // Previews are never cached
+        store.insert_preview_token("draft", "token");
+        assert!(get("/blog/draft?preview=token")
+            .await
+            .1
+            .contains("Contents of draft"));
+        let changed_draft = BlogPost {
+            html: "<p>Changed draft</p>".to_string(),
+            ..BlogPost::test_post("draft", PostStatus::Draft, None)
+        };
+        store
+            .update_blog_post(None, &changed_draft, &changed_draft.authors[0], &[])
+            .await
+            .unwrap();
+        assert!(get("/blog/draft?preview=token")
+            .await
+            .1
+            .contains("Changed draft"));
+    }
+
+    #[tokio::test]
+    async fn blog_post_test() {
+        let store = store().await;
+
+        let (_, body) = get(store.clone(), "/blog/public").await;
+        assert!(body.contains("<p>Contents of public</p>"));
+
+        // Unlisted posts need a preview token as well, and are never listed
+        let unlisted = BlogPost::test_post("unlisted", PostStatus::Unlisted, None);
+        store.insert_blog_post(&unlisted, &[]).await.unwrap();
+        let (response, _) = get(store.clone(), "/blog/unlisted").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        store.insert_preview_token("unlisted", "unlisted_token");
+        let (response, body) = get(store.clone(), "/blog/unlisted?preview=unlisted_token").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body.contains("<p>Contents of unlisted</p>"));
+        assert_eq!(response.headers()[X_ROBOTS_TAG], "noindex, nofollow");
+        let (_, body) = get(store.clone(), "/blog").await;
+        assert!(!body.contains("Title of unlisted"));
+
+        // Drafts need a preview token
+        let (_, body) = get(store.clone(), "/blog/draft").await;
+        assert!(!body.contains("Contents of draft"));
+        let (_, body) = get(store.clone(), "/blog/draft?preview=token").await;
+        assert!(!body.contains("Contents of draft"));
+
+        store.insert_preview_token("draft", "token");
+        let (_, body) = get(store, "/blog/draft?preview=token").await;
+        assert!(body.contains("<p>Contents of draft</p>"));
+    }
+
+    #[tokio::test]
+    async fn redirect_test() {
+        let store = store().await;
+        let renamed = BlogPost::test_post("renamed", PostStatus::Published, Some(0));
+        store
+            .update_blog_post(Some("public"), &renamed, &renamed.authors[0], &[])
+            .await
+            .unwrap();
+
+        let (response, _) = get(store, "/blog/public").await;
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+        let location = response.headers()[LOCATION].to_str().unwrap();
+        assert!(location.starts_with("/blog/renamed"));
+    }
+
+    #[tokio::test]
+    async fn author_names_test() {
+        let store = store().await;
+        store.insert_author(AuthorProfile {
+            author: Author("Quiet".to_string()),
+            display_name: Some("Quiet Person".to_string()),
+            bio_markdown: None,
+            bio_html: None,
+            avatar_path: None,
+            links: Vec::new(),
+        });
+
+        for uri in [
+            "/blog",
+            "/blog/public",
+            "/blog/search?q=contents",
+            "/blog/rss.xml",
+        ] {
+            let (_, body) = get(store.clone(), uri).await;
+            assert!(body.contains("Quiet Person"), "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn search_test() {
+        let (response, body) = get(store().await, "/blog/search?q=contents").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Results are shown like every other post, with the snippet in place of the description
+        assert!(body.contains("Title of public"));
+        assert!(body.contains("#post"));
+        assert!(body.contains("1 minute read"));
+        assert!(body.contains("Description of public"));
+        assert!(!body.contains("Title of draft"));
+    }
+
+    #[tokio::test]
+    async fn rss_test() {
+        let (_, body) = get(store().await, "/blog/rss.xml").await;
+
+        assert!(body.contains("<link>https://example.com/blog/public</link>"));
+        assert!(body.contains("Description of public (3 words, 1 minute read)"));
+        assert!(!body.contains("draft"));
+    }
 }
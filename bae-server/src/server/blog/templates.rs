@@ -1,18 +1,185 @@
 use crate::model::ServerPathExt;
+use crate::server::blog::PageQueryParams;
 use askama::Template;
-use bae_common::blog::BlogPost;
-use bae_common::database::Tag;
+use axum_extra::routing::TypedPath;
+use bae_common::blog::{AuthorProfile, BlogPost, SearchResult, SeriesPart, SnippetFragment};
+use bae_common::database::{Author, Language, Page, Series, Tag};
+use bae_common::markdown_render::Heading;
+use std::collections::HashMap;
+
+/// Display names of authors, authors without a profile are shown as they are.
+#[derive(Clone, Default)]
+pub struct AuthorNames(HashMap<Author, String>);
+
+impl AuthorNames {
+    pub fn new(author_profiles: Vec<AuthorProfile>) -> Self {
+        AuthorNames(
+            author_profiles
+                .into_iter()
+                .map(|profile| (profile.author.clone(), profile.name().to_string()))
+                .collect(),
+        )
+    }
+
+    pub fn name<'a>(&'a self, author: &'a Author) -> &'a str {
+        self.0.get(author).map_or(&author.0, String::as_str)
+    }
+
+    pub fn join(&self, authors: &[Author]) -> String {
+        authors
+            .iter()
+            .map(|author| self.name(author))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Passed to the `display_post` macro for posts that are not search results, which show their
+/// description instead of a snippet.
+pub const NO_SNIPPET: &[SnippetFragment] = &[];
 
 #[derive(Template)]
 #[template(path = "blog/home.html")]
 pub struct HomeTemplate {
+    /// Empty on all but the first page.
+    pub featured_posts: Vec<BlogPost>,
     pub blog_posts: Vec<BlogPost>,
+    pub page_links: PageLinks,
+    pub language_links: LanguageLinks,
+    pub author_names: AuthorNames,
+}
+
+#[derive(Clone, Default)]
+pub struct PageLinks {
+    pub previous: Option<String>,
+    pub next: Option<String>,
+}
+
+impl PageLinks {
+    /// `lang` is the language filter of the current page, which is kept when paging.
+    pub fn new<P: TypedPath + Clone>(path: P, page: &Page, lang: Option<Language>) -> Self {
+        let link = |params| path.clone().with_query_params(params).to_string();
+
+        PageLinks {
+            previous: page.previous.clone().map(|before| {
+                link(PageQueryParams {
+                    before: Some(before),
+                    lang: lang.clone(),
+                    ..Default::default()
+                })
+            }),
+            next: page.next.clone().map(|after| {
+                link(PageQueryParams {
+                    page: Some(after),
+                    lang: lang.clone(),
+                    ..Default::default()
+                })
+            }),
+        }
+    }
+}
+
+/// Links to the first page of a listing filtered to each language.
+#[derive(Clone, Default)]
+pub struct LanguageLinks {
+    /// Link to the unfiltered listing.
+    pub all: String,
+    pub languages: Vec<(Language, String)>,
+    pub current: Option<Language>,
+}
+
+impl LanguageLinks {
+    pub fn new<P: TypedPath + Clone>(
+        path: P,
+        languages: Vec<Language>,
+        current: Option<Language>,
+    ) -> Self {
+        let link = |lang| {
+            path.clone()
+                .with_query_params(PageQueryParams {
+                    lang,
+                    ..Default::default()
+                })
+                .to_string()
+        };
+
+        LanguageLinks {
+            all: link(None),
+            languages: languages
+                .into_iter()
+                .map(|language| (language.clone(), link(Some(language))))
+                .collect(),
+            current,
+        }
+    }
 }
 
 #[derive(Template)]
 #[template(path = "blog/blog_post.html")]
 pub struct BlogPostTemplate {
     pub blog_post: BlogPost,
+    pub series: Option<SeriesNavigation>,
+    pub table_of_contents: Option<TableOfContents>,
+    /// Other public translations of the post.
+    pub translations: Vec<BlogPost>,
+    pub base_uri: String,
+    pub author_names: AuthorNames,
+}
+
+pub struct TableOfContents {
+    /// Headings with their depth below the highest level heading of the post.
+    pub entries: Vec<(u8, Heading)>,
+}
+
+impl TableOfContents {
+    /// `None` if the post has its table of contents disabled or has no headings.
+    pub fn new(blog_post: &BlogPost) -> Option<Self> {
+        if !blog_post.table_of_contents {
+            return None;
+        }
+
+        let top_level = blog_post
+            .outline
+            .iter()
+            .map(|heading| heading.level)
+            .min()?;
+        let entries = blog_post
+            .outline
+            .iter()
+            .map(|heading| (heading.level - top_level, heading.clone()))
+            .collect();
+
+        Some(TableOfContents { entries })
+    }
+}
+
+pub struct SeriesNavigation {
+    pub series: Series,
+    pub part: u32,
+    pub parts: u32,
+    pub previous: Option<BlogPost>,
+    pub next: Option<BlogPost>,
+}
+
+impl SeriesNavigation {
+    /// `series_posts` has to be ordered by part number.
+    pub fn new(SeriesPart { series, part }: &SeriesPart, series_posts: Vec<BlogPost>) -> Self {
+        let part_of = |post: &BlogPost| post.series.as_ref().map_or(0, |series| series.part);
+
+        let parts = series_posts.last().map_or(0, part_of).max(*part);
+        let (previous, rest): (Vec<_>, Vec<_>) = series_posts
+            .into_iter()
+            .filter(|post| part_of(post) != *part)
+            .partition(|post| part_of(post) < *part);
+
+        SeriesNavigation {
+            series: series.clone(),
+            part: *part,
+            parts,
+            previous: previous.into_iter().last(),
+            next: rest.into_iter().next(),
+        }
+    }
 }
 
 #[derive(Template)]
@@ -20,6 +187,34 @@ pub struct BlogPostTemplate {
 pub struct TaggedTemplate {
     pub tag: Tag,
     pub blog_posts: Vec<BlogPost>,
+    pub page_links: PageLinks,
+    pub author_names: AuthorNames,
+}
+
+#[derive(Template)]
+#[template(path = "blog/author.html")]
+pub struct AuthorTemplate {
+    pub author_profile: AuthorProfile,
+    pub blog_posts: Vec<BlogPost>,
+    pub page_links: PageLinks,
+    pub rss_path: String,
+    pub author_names: AuthorNames,
+}
+
+#[derive(Template)]
+#[template(path = "blog/series.html")]
+pub struct SeriesTemplate {
+    pub series: Series,
+    pub blog_posts: Vec<BlogPost>,
+    pub author_names: AuthorNames,
+}
+
+#[derive(Template)]
+#[template(path = "blog/search.html")]
+pub struct SearchTemplate {
+    pub query: String,
+    pub results: Vec<SearchResult>,
+    pub author_names: AuthorNames,
 }
 
 #[derive(Template)]
@@ -0,0 +1,175 @@
+use bae_common::database::BlogStore;
+use sqlx::postgres::PgListener;
+use sqlx::types::chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use tracing::{debug, error, warn};
+
+/// Keeps memory bounded, every tag, author and page boundary in the query makes a page of its own.
+const MAX_CACHED_PAGES: usize = 1024;
+
+/// How long to wait before listening again after the notification connection failed.
+const RELISTEN_DELAY: Duration = Duration::from_secs(5);
+
+/// Rendered pages by path and parsed query parameters, so unknown or reordered parameters don't
+/// add pages.
+///
+/// Posts only change when the CLI writes them, which notifies the server, see
+/// [`invalidate_on_notifications`]. Scheduled posts become public without any write, so the cache
+/// is also cleared at the next publication date.
+#[derive(Debug)]
+pub struct PageCache {
+    enabled: bool,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    pages: HashMap<String, String>,
+    /// Incremented on every invalidation, so pages rendered before it are not inserted after it.
+    generation: u64,
+    expiry: Expiry,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+enum Expiry {
+    /// Looked up when the next page is inserted.
+    #[default]
+    Unknown,
+    Never,
+    At(DateTime<Utc>),
+}
+
+/// A page that was not cached, to be inserted once it is rendered.
+#[derive(Debug)]
+pub struct Miss {
+    key: String,
+    generation: u64,
+    looked_up_at: DateTime<Utc>,
+}
+
+impl PageCache {
+    pub fn new() -> Self {
+        PageCache {
+            enabled: true,
+            state: Mutex::default(),
+        }
+    }
+
+    /// A cache that never stores anything, for backends that can't notify about changes.
+    pub fn disabled() -> Self {
+        PageCache {
+            enabled: false,
+            state: Mutex::default(),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // The state is consistent after every statement, a panic can't leave it broken
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn get(&self, key: String) -> Result<String, Miss> {
+        let looked_up_at = Utc::now();
+        let mut state = self.state();
+
+        if matches!(state.expiry, Expiry::At(date) if date <= looked_up_at) {
+            debug!("Scheduled post became public, clearing page cache");
+            state.clear();
+        }
+
+        match state.pages.get(&key) {
+            Some(page) => Ok(page.clone()),
+            None => Err(Miss {
+                key,
+                generation: state.generation,
+                looked_up_at,
+            }),
+        }
+    }
+
+    pub async fn insert(&self, miss: Miss, page: String, database: &dyn BlogStore) {
+        if !self.enabled {
+            return;
+        }
+
+        let Miss {
+            key,
+            generation,
+            looked_up_at,
+        } = miss;
+
+        let expiry = self.state().expiry;
+        let expiry = match expiry {
+            // Publications after the lookup might already be part of the page
+            Expiry::Unknown => match database.get_next_publication_date(looked_up_at).await {
+                Ok(Some(date)) => Expiry::At(date),
+                Ok(None) => Expiry::Never,
+                Err(error) => {
+                    warn!(%error, "Getting the next publication date failed, not caching page");
+                    return;
+                }
+            },
+            expiry => expiry,
+        };
+
+        let mut state = self.state();
+        if state.generation != generation {
+            return;
+        }
+
+        // Any page will do, the cache only has to stay bounded
+        if state.pages.len() >= MAX_CACHED_PAGES {
+            if let Some(evicted) = state.pages.keys().next().cloned() {
+                state.pages.remove(&evicted);
+            }
+        }
+
+        state.expiry = expiry;
+        state.pages.insert(key, page);
+    }
+
+    pub fn invalidate(&self) {
+        self.state().clear();
+    }
+}
+
+impl Default for PageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State {
+    fn clear(&mut self) {
+        self.pages.clear();
+        self.generation += 1;
+        self.expiry = Expiry::Unknown;
+    }
+}
+
+/// Clears `page_cache` whenever a post changes. Notifications sent while the connection is lost
+/// are missed, so it is cleared when that happens as well.
+pub async fn invalidate_on_notifications(mut listener: PgListener, page_cache: Arc<PageCache>) {
+    loop {
+        match listener.try_recv().await {
+            Ok(Some(notification)) => {
+                debug!(
+                    url = notification.payload(),
+                    "Blog post changed, clearing page cache"
+                );
+                page_cache.invalidate();
+            }
+            Ok(None) => {
+                warn!("Lost connection for blog post notifications, clearing page cache");
+                page_cache.invalidate();
+            }
+            Err(error) => {
+                error!(%error, "Receiving blog post notifications failed, clearing page cache");
+                page_cache.invalidate();
+                tokio::time::sleep(RELISTEN_DELAY).await;
+            }
+        }
+    }
+}
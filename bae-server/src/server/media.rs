@@ -0,0 +1,58 @@
+use crate::server::{Error, Result};
+use crate::AppState;
+use axum::extract::State;
+use axum::http::header::{
+    CACHE_CONTROL, CONTENT_SECURITY_POLICY, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS,
+};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use axum_extra::routing::{RouterExt, TypedPath};
+use bae_common::database::BlogStore;
+use bae_common::media::MediaAsset;
+use serde::Deserialize;
+use std::sync::Arc;
+
+pub fn router() -> Router<AppState> {
+    Router::new().typed_get(media)
+}
+
+/// Has to match [`MediaAsset::path`].
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/media/{file_name}", rejection(Error))]
+pub struct MediaPath {
+    pub file_name: String,
+}
+
+pub async fn media(
+    MediaPath { file_name }: MediaPath,
+    State(database): State<Arc<dyn BlogStore>>,
+) -> Result<Response> {
+    let (hash, _) = file_name.split_once('.').ok_or(Error::NotFound)?;
+
+    let media_asset = database
+        .get_media_asset(hash)
+        .await?
+        .filter(|media_asset| media_asset.file_name() == file_name)
+        .ok_or(Error::NotFound)?;
+
+    let MediaAsset {
+        content_type, data, ..
+    } = media_asset;
+
+    // Assets are content-addressed, so they never change.
+    // SVGs can contain scripts, which the CSP keeps from running when opened directly.
+    let headers = [
+        (CONTENT_TYPE, content_type),
+        (
+            CACHE_CONTROL,
+            "public, max-age=31536000, immutable".to_string(),
+        ),
+        (X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        (
+            CONTENT_SECURITY_POLICY,
+            "default-src 'none'; style-src 'unsafe-inline'; sandbox".to_string(),
+        ),
+    ];
+
+    Ok((headers, data).into_response())
+}
@@ -1,18 +1,24 @@
 pub mod blog;
+pub mod cache;
+pub mod media;
 pub mod templates;
 pub mod util;
 
+use crate::server::blog::templates::AuthorNames;
 use crate::AppState;
 use askama::Template;
 use axum::extract::rejection::PathRejection;
+use axum::extract::State;
 use axum::handler::HandlerWithoutStateExt;
 use axum::http::StatusCode;
 use axum::response::{Html, IntoResponse, Response};
 use axum::Router;
 use axum_extra::routing::{RouterExt, TypedPath};
 use bae_common::database;
+use bae_common::database::BlogStore;
 use serde::Deserialize;
 use std::path::Path;
+use std::sync::Arc;
 use templates::{ErrorTemplate, HomeTemplate};
 use thiserror::Error;
 use tower_http::services::ServeDir;
@@ -38,6 +44,7 @@ impl Error {
     pub fn status(&self) -> StatusCode {
         match self {
             Error::NotFound | Error::Path(_) => StatusCode::NOT_FOUND,
+            Error::Database(database::Error::Unsupported) => StatusCode::NOT_IMPLEMENTED,
             Error::Askama(_) | Error::Database(_) | Error::InvalidDatabaseReturn => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
@@ -52,7 +59,7 @@ impl IntoResponse for Error {
         error!(error = %self, %status, "Replying with error");
 
         match (ErrorTemplate { status }.render()) {
-            Ok(html) => Html(html).into_response(),
+            Ok(html) => (status, Html(html)).into_response(),
             Err(error) => {
                 error!(%error, "Error trying to reply with error");
                 (status, Html(format!("Error code {status}"))).into_response()
@@ -65,6 +72,7 @@ pub fn router(static_path: impl AsRef<Path>) -> Router<AppState> {
     Router::new()
         .typed_get(home)
         .merge(blog::router())
+        .merge(media::router())
         .fallback_service(
             ServeDir::new(static_path).fallback((|| async { Error::NotFound }).into_service()),
         )
@@ -74,7 +82,16 @@ pub fn router(static_path: impl AsRef<Path>) -> Router<AppState> {
 #[typed_path("/", rejection(Error))]
 pub struct HomePath {}
 
-pub async fn home(HomePath {}: HomePath) -> Result<Html<String>> {
-    let html = HomeTemplate {}.render()?;
+pub async fn home(
+    HomePath {}: HomePath,
+    State(database): State<Arc<dyn BlogStore>>,
+) -> Result<Html<String>> {
+    let featured_posts = blog::featured_posts(&*database, None).await?;
+
+    let html = HomeTemplate {
+        featured_posts,
+        author_names: AuthorNames::new(database.get_authors().await?),
+    }
+    .render()?;
     Ok(Html(html))
 }
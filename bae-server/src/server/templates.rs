@@ -1,5 +1,8 @@
+use crate::model::ServerPathExt;
+use crate::server::blog::templates::AuthorNames;
 use askama::Template;
 use axum::http::StatusCode;
+use bae_common::blog::BlogPost;
 
 #[derive(Template)]
 #[template(path = "error.html")]
@@ -9,4 +12,7 @@ pub struct ErrorTemplate {
 
 #[derive(Template)]
 #[template(path = "home.html")]
-pub struct HomeTemplate {}
+pub struct HomeTemplate {
+    pub featured_posts: Vec<BlogPost>,
+    pub author_names: AuthorNames,
+}